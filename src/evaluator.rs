@@ -0,0 +1,545 @@
+//! CUTEst-like evaluation of a parsed problem.
+//!
+//! An [`Evaluator`] is built from a [`SIF`] and evaluates the objective, the
+//! constraints and their derivatives following the LANCELOT group partially
+//! separable structure used by CUTEst:
+//!
+//! ```text
+//! α_i(x) = a_iᵀx + Σ_e w_e f_e(x_e) − b_i        group argument
+//! group_i(x) = g_i(α_i(x)) / s_i                  group value
+//! f(x) = group_0(x) + ½ xᵀQx                      objective (row 0)
+//! c_i(x) = group_i(x)                             constraints (other rows)
+//! ```
+//!
+//! where `a_i` are the linear entries of row `i`, `f_e` its nonlinear
+//! elements with weights `w_e`, `b_i` its right-hand side, `g_i` its group
+//! function (the identity for trivial groups) and `s_i` its `'SCALE'`. Since
+//! the constant is inside the group, constraint bounds are relative to it:
+//! an equality row reads `c_i(x) = 0`. The Lagrangian is `f(x) + yᵀc(x)`.
+//!
+//! Row 0 is the objective row of [`SIF::get_objective_name`]. Other free
+//! (`N`) rows are neither objective nor constraints.
+//!
+//! The problem is always presented as a minimization: when the file declares
+//! `OBJSENSE MAX`, `f` is the negated objective.
+
+use std::collections::{BTreeSet, HashMap};
+
+use crate::{
+    ParseError, SIF, check_length,
+    functions::{ElementFunction, GroupFunction},
+    types::{ObjSense, RowType},
+};
+
+/// A nonlinear element bound to problem variables.
+struct Element {
+    /// Index into the element functions.
+    function: usize,
+    /// Variable indices in the order of the elemental variables.
+    variables: Vec<usize>,
+    /// Parameter values in the order of the element parameters.
+    parameters: Vec<f64>,
+}
+
+/// A group (row) of the problem.
+struct Group {
+    linear: Vec<(usize, f64)>,
+    constant: f64,
+    scale: f64,
+    /// Indices into the elements, with their weights.
+    elements: Vec<(usize, f64)>,
+    /// Index into the group functions and the group parameter values, or
+    /// `None` for a trivial group.
+    function: Option<(usize, Vec<f64>)>,
+    /// Sorted indices of the variables the group depends on.
+    variables: Vec<usize>,
+}
+
+impl Group {
+    fn is_linear(&self) -> bool {
+        self.elements.is_empty() && self.function.is_none()
+    }
+
+    fn position(&self, variable: usize) -> usize {
+        self.variables.binary_search(&variable).unwrap()
+    }
+}
+
+/// Evaluates the objective, constraints and derivatives of a problem.
+///
+/// Variables are in file order, like [`SIF::get_col_names`], and constraints
+/// are the non-free rows of [`SIF::get_row_names`]. Sparse Jacobian and Hessian values are
+/// returned in the order of fixed sparsity patterns, given once by
+/// [`Evaluator::get_jacobian_structure`] and
+/// [`Evaluator::get_hessian_structure`].
+///
+/// # Example
+///
+/// ```no_run
+/// let sif = sif_rs::parse_file("examples/qptest.sif").unwrap();
+/// let evaluator = sif_rs::evaluator::Evaluator::new(&sif).unwrap();
+/// let x = evaluator.get_initial_point().clone();
+/// let f = evaluator.obj(&x).unwrap();
+/// ```
+pub struct Evaluator {
+    variable_names: Vec<String>,
+    constraint_names: Vec<String>,
+    variable_bounds: Vec<(f64, f64)>,
+    constraint_bounds: Vec<(f64, f64)>,
+    initial_point: Vec<f64>,
+
+    element_functions: Vec<ElementFunction>,
+    group_functions: Vec<GroupFunction>,
+    elements: Vec<Element>,
+    /// Group of the objective row, if the problem has one.
+    objective: Option<Group>,
+    constraints: Vec<Group>,
+    /// Lower triangle of the quadratic objective matrix Q: `(i, j, q)` with
+    /// `i >= j`, already negated for a maximization problem.
+    quadratic: Vec<(usize, usize, f64)>,
+    /// `-1` for a maximization problem, `1` otherwise.
+    sense: f64,
+
+    jacobian_structure: Vec<(usize, usize)>,
+    hessian_structure: Vec<(usize, usize)>,
+    hessian_index: HashMap<(usize, usize), usize>,
+}
+
+impl Evaluator {
+    /// Builds an evaluator for `sif`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if an element or group type used by the
+    /// problem has no function in the `ELEMENTS` or `GROUPS` section.
+    pub fn new(sif: &SIF) -> Result<Evaluator, ParseError> {
+        let variable_names: Vec<String> = sif.get_col_names().clone();
+        let variable_index: HashMap<&str, usize> = variable_names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| (name.as_str(), index))
+            .collect();
+
+        let element_functions: Vec<ElementFunction> =
+            sif.get_element_functions().values().cloned().collect();
+        let group_functions: Vec<GroupFunction> =
+            sif.get_group_functions().values().cloned().collect();
+
+        let mut elements = Vec::new();
+        let mut element_index: HashMap<&str, usize> = HashMap::new();
+        for (name, element) in sif.get_element_uses() {
+            let element_type = &sif.get_element_types()[element.get_element_type()];
+            let function = element_functions
+                .iter()
+                .position(|function| function.get_name() == element_type.get_name())
+                .ok_or_else(|| ParseError {
+                    message: format!(
+                        "Missing element function for element type: {}",
+                        element_type.get_name()
+                    ),
+                })?;
+
+            element_index.insert(name, elements.len());
+            elements.push(Element {
+                function,
+                variables: element_type
+                    .get_elemental_variables()
+                    .iter()
+                    .map(|variable| variable_index[element.get_variables()[variable].as_str()])
+                    .collect(),
+                parameters: element_type
+                    .get_parameters()
+                    .iter()
+                    .map(|parameter| element.get_parameters()[parameter])
+                    .collect(),
+            });
+        }
+
+        let constraint_bounds_by_row = sif.get_constraint_bounds();
+        let mut objective = None;
+        let mut constraints = Vec::new();
+        let mut constraint_names = Vec::new();
+        let mut constraint_bounds = Vec::new();
+
+        let mut entries: HashMap<&str, Vec<(usize, f64)>> = HashMap::new();
+        for ((row_name, col_name), value) in sif.get_entries() {
+            entries
+                .entry(row_name)
+                .or_default()
+                .push((variable_index[col_name.as_str()], *value));
+        }
+
+        for row_name in sif.get_row_names() {
+            let row_type = &sif.get_rows()[row_name];
+            let linear = entries.remove(row_name.as_str()).unwrap_or_default();
+            let group_use = sif.get_group_use(row_name);
+
+            let group_elements: Vec<(usize, f64)> = group_use
+                .map(|group| {
+                    group
+                        .get_elements()
+                        .iter()
+                        .map(|(name, weight)| (element_index[name.as_str()], *weight))
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            let function = match group_use.filter(|group| !group.get_group_type().is_empty()) {
+                Some(group) => {
+                    let group_type = &sif.get_group_types()[group.get_group_type()];
+                    let function = group_functions
+                        .iter()
+                        .position(|function| function.get_name() == group_type.get_name())
+                        .ok_or_else(|| ParseError {
+                            message: format!(
+                                "Missing group function for group type: {}",
+                                group_type.get_name()
+                            ),
+                        })?;
+                    let parameters = group_type
+                        .get_parameters()
+                        .iter()
+                        .map(|parameter| group.get_parameters()[parameter])
+                        .collect();
+                    Some((function, parameters))
+                }
+                None => None,
+            };
+
+            let variables: BTreeSet<usize> = linear
+                .iter()
+                .map(|(variable, _)| *variable)
+                .chain(
+                    group_elements
+                        .iter()
+                        .flat_map(|(element, _)| elements[*element].variables.clone()),
+                )
+                .collect();
+
+            let constant = sif.get_row_constant(row_name);
+            let scale = sif.get_row_scales().get(row_name).copied().unwrap_or(1.0);
+            let group = Group {
+                linear,
+                constant,
+                scale,
+                elements: group_elements,
+                function,
+                variables: variables.into_iter().collect(),
+            };
+
+            if *row_type == RowType::N {
+                if sif.get_objective_name() == Some(row_name.as_str()) {
+                    objective = Some(group);
+                }
+            } else {
+                // Bounds of the group value, which already contains the
+                // constant and the scale.
+                let (lower, upper) = constraint_bounds_by_row[row_name];
+                let (lower, upper) = ((lower - constant) / scale, (upper - constant) / scale);
+                constraint_bounds.push(if scale < 0.0 {
+                    (upper, lower)
+                } else {
+                    (lower, upper)
+                });
+                constraint_names.push(row_name.clone());
+                constraints.push(group);
+            }
+        }
+
+        // Variables are in file order, like the columns of Q.
+        let mut quadratic = sif.get_quadratic_coo();
+        quadratic.sort_by_key(|(i, j, _)| (*i, *j));
+
+        let mut jacobian_structure = Vec::new();
+        for (i, group) in constraints.iter().enumerate() {
+            jacobian_structure.extend(group.variables.iter().map(|j| (i, *j)));
+        }
+
+        let mut hessian_entries: BTreeSet<(usize, usize)> =
+            quadratic.iter().map(|(i, j, _)| (*i, *j)).collect();
+        for group in objective.iter().chain(&constraints) {
+            let blocks: Vec<&Vec<usize>> = if group.function.is_some() {
+                vec![&group.variables]
+            } else {
+                group
+                    .elements
+                    .iter()
+                    .map(|(element, _)| &elements[*element].variables)
+                    .collect()
+            };
+            for variables in blocks {
+                for i in variables {
+                    for j in variables {
+                        if i >= j {
+                            hessian_entries.insert((*i, *j));
+                        }
+                    }
+                }
+            }
+        }
+        let hessian_structure: Vec<(usize, usize)> = hessian_entries.into_iter().collect();
+        let hessian_index = hessian_structure
+            .iter()
+            .enumerate()
+            .map(|(position, entry)| (*entry, position))
+            .collect();
+
+        let variable_bounds = variable_names
+            .iter()
+            .map(|name| sif.get_column_bounds(name).unwrap_or((0.0, f64::INFINITY)))
+            .collect();
+
+        Ok(Evaluator {
+            variable_names,
+            constraint_names,
+            variable_bounds,
+            constraint_bounds,
+            initial_point: sif.get_start_point(),
+            element_functions,
+            group_functions,
+            elements,
+            objective,
+            constraints,
+            quadratic,
+            sense: match sif.get_objective_sense() {
+                ObjSense::Min => 1.0,
+                ObjSense::Max => -1.0,
+            },
+            jacobian_structure,
+            hessian_structure,
+            hessian_index,
+        })
+    }
+
+    /// Returns the number of variables.
+    pub fn get_num_variables(&self) -> usize {
+        self.variable_names.len()
+    }
+
+    /// Returns the number of constraints.
+    pub fn get_num_constraints(&self) -> usize {
+        self.constraint_names.len()
+    }
+
+    /// Returns the variable names in evaluation order.
+    pub fn get_variable_names(&self) -> &Vec<String> {
+        &self.variable_names
+    }
+
+    /// Returns the constraint (row) names in evaluation order.
+    pub fn get_constraint_names(&self) -> &Vec<String> {
+        &self.constraint_names
+    }
+
+    /// Returns the `(lower, upper)` bounds of every variable.
+    pub fn get_variable_bounds(&self) -> &Vec<(f64, f64)> {
+        &self.variable_bounds
+    }
+
+    /// Returns the `(lower, upper)` bounds of every constraint value `c_i(x)`.
+    ///
+    /// The bounds are those of [`SIF::get_constraint_bounds`] shifted by the
+    /// right-hand side and divided by the scale of the group.
+    pub fn get_constraint_bounds(&self) -> &Vec<(f64, f64)> {
+        &self.constraint_bounds
+    }
+
+    /// Returns the start point of the active `START POINT` set.
+    pub fn get_initial_point(&self) -> &Vec<f64> {
+        &self.initial_point
+    }
+
+    /// Returns the `(constraint, variable)` positions of the Jacobian
+    /// entries, sorted by constraint and then variable.
+    pub fn get_jacobian_structure(&self) -> &Vec<(usize, usize)> {
+        &self.jacobian_structure
+    }
+
+    /// Returns the `(row, col)` positions of the lower triangle (`row >= col`)
+    /// of the Hessian of the Lagrangian, sorted by row and then column.
+    pub fn get_hessian_structure(&self) -> &Vec<(usize, usize)> {
+        &self.hessian_structure
+    }
+
+    /// Returns the objective value f(x), negated for a maximization problem.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if `x` does not have one value per variable,
+    /// like the other evaluation methods.
+    pub fn obj(&self, x: &[f64]) -> Result<f64, ParseError> {
+        self.check_point(x)?;
+
+        let groups = self
+            .objective
+            .iter()
+            .map(|group| Ok(self.evaluate_group(group, x, 0)?.0))
+            .sum::<Result<f64, ParseError>>()?;
+        let quadratic: f64 = self
+            .quadratic
+            .iter()
+            .map(|(i, j, q)| {
+                if i == j {
+                    0.5 * q * x[*i] * x[*i]
+                } else {
+                    q * x[*i] * x[*j]
+                }
+            })
+            .sum();
+
+        Ok(self.sense * groups + quadratic)
+    }
+
+    /// Returns the dense gradient ∇f(x) of the objective.
+    pub fn grad(&self, x: &[f64]) -> Result<Vec<f64>, ParseError> {
+        self.check_point(x)?;
+        let mut gradient = vec![0.0; self.get_num_variables()];
+
+        if let Some(group) = &self.objective {
+            let (_, group_gradient, _) = self.evaluate_group(group, x, 1)?;
+            for (variable, value) in group.variables.iter().zip(group_gradient) {
+                gradient[*variable] += self.sense * value;
+            }
+        }
+        for (i, j, q) in &self.quadratic {
+            gradient[*i] += q * x[*j];
+            if i != j {
+                gradient[*j] += q * x[*i];
+            }
+        }
+
+        Ok(gradient)
+    }
+
+    /// Returns the constraint values c(x).
+    pub fn cons(&self, x: &[f64]) -> Result<Vec<f64>, ParseError> {
+        self.check_point(x)?;
+        self.constraints
+            .iter()
+            .map(|group| Ok(self.evaluate_group(group, x, 0)?.0))
+            .collect()
+    }
+
+    /// Returns the values of the Jacobian of c(x), in the order of
+    /// [`Evaluator::get_jacobian_structure`].
+    pub fn jac(&self, x: &[f64]) -> Result<Vec<f64>, ParseError> {
+        self.check_point(x)?;
+        let mut jacobian = Vec::with_capacity(self.jacobian_structure.len());
+        for group in &self.constraints {
+            jacobian.extend(self.evaluate_group(group, x, 1)?.1);
+        }
+        Ok(jacobian)
+    }
+
+    /// Returns the values of the lower triangle of the Hessian of the
+    /// Lagrangian `f(x) + yᵀc(x)`, in the order of
+    /// [`Evaluator::get_hessian_structure`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if `x` does not have one value per variable
+    /// or `y` one multiplier per constraint.
+    pub fn hess_lagrangian(&self, x: &[f64], y: &[f64]) -> Result<Vec<f64>, ParseError> {
+        self.check_point(x)?;
+        check_length("constraint multipliers", y, self.get_num_constraints())?;
+        let mut hessian = vec![0.0; self.hessian_structure.len()];
+
+        for (i, j, q) in &self.quadratic {
+            hessian[self.hessian_index[&(*i, *j)]] += q;
+        }
+
+        let groups = self
+            .objective
+            .iter()
+            .map(|group| (group, self.sense))
+            .chain(self.constraints.iter().zip(y.iter().copied()));
+        for (group, multiplier) in groups {
+            if group.is_linear() || multiplier == 0.0 {
+                continue;
+            }
+
+            let (_, _, group_hessian) = self.evaluate_group(group, x, 2)?;
+            for (k, i) in group.variables.iter().enumerate() {
+                for (l, j) in group.variables.iter().enumerate().take(k + 1) {
+                    if group_hessian[k][l] != 0.0 {
+                        hessian[self.hessian_index[&(*i, *j)]] += multiplier * group_hessian[k][l];
+                    }
+                }
+            }
+        }
+
+        Ok(hessian)
+    }
+
+    /// Returns an error unless `x` has one value per variable.
+    fn check_point(&self, x: &[f64]) -> Result<(), ParseError> {
+        check_length("variable values", x, self.get_num_variables())
+    }
+
+    /// Evaluates a group and, up to `order`, its gradient and Hessian with
+    /// respect to the group variables.
+    #[allow(clippy::type_complexity)]
+    fn evaluate_group(
+        &self,
+        group: &Group,
+        x: &[f64],
+        order: usize,
+    ) -> Result<(f64, Vec<f64>, Vec<Vec<f64>>), ParseError> {
+        let n = group.variables.len();
+        let mut alpha = -group.constant;
+        let mut gradient = vec![0.0; if order > 0 { n } else { 0 }];
+        let mut hessian = vec![vec![0.0; n]; if order > 1 { n } else { 0 }];
+
+        for (variable, value) in &group.linear {
+            alpha += value * x[*variable];
+            if order > 0 {
+                gradient[group.position(*variable)] += value;
+            }
+        }
+
+        for (element, weight) in &group.elements {
+            let element = &self.elements[*element];
+            let values: Vec<f64> = element.variables.iter().map(|j| x[*j]).collect();
+            let (f, g, h) =
+                self.element_functions[element.function].evaluate(&values, &element.parameters)?;
+
+            alpha += weight * f;
+            let positions: Vec<usize> = element
+                .variables
+                .iter()
+                .map(|variable| group.position(*variable))
+                .collect();
+            if order > 0 {
+                for (k, position) in positions.iter().enumerate() {
+                    gradient[*position] += weight * g[k];
+                }
+            }
+            if order > 1 {
+                for (k, row) in positions.iter().enumerate() {
+                    for (l, col) in positions.iter().enumerate() {
+                        hessian[*row][*col] += weight * h[k][l];
+                    }
+                }
+            }
+        }
+
+        let (g, dg, d2g) = match &group.function {
+            Some((function, parameters)) => {
+                self.group_functions[*function].evaluate(alpha, parameters)?
+            }
+            None => (alpha, 1.0, 0.0),
+        };
+
+        let s = group.scale;
+        for k in 0..hessian.len() {
+            for l in 0..n {
+                hessian[k][l] = (d2g * gradient[k] * gradient[l] + dg * hessian[k][l]) / s;
+            }
+        }
+        for value in &mut gradient {
+            *value *= dg / s;
+        }
+
+        Ok((g / s, gradient, hessian))
+    }
+}
@@ -0,0 +1,405 @@
+//! Fortran expressions, as written in the `ELEMENTS` and `GROUPS` function
+//! sections of a SIF file.
+//!
+//! Expressions are parsed once into a tree and evaluated against a scope that
+//! maps names (variables, parameters and temporaries) to values. Names are
+//! case-insensitive, as in Fortran, and are read in upper case. Arithmetic
+//! follows Fortran: `**` binds tighter than a leading sign and is right
+//! associative, and the quotient of two integer operands is truncated.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::ParseError;
+
+/// A binary arithmetic operator.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Op {
+    Add,
+    Sub,
+    Mul,
+    /// Division of real operands.
+    Div,
+    /// Division of integer operands, truncated towards zero.
+    IntDiv,
+    Pow,
+}
+
+/// A parsed Fortran expression.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Expr {
+    Number(f64),
+    /// A name, in upper case.
+    Name(String),
+    Neg(Box<Expr>),
+    Binary(Op, Box<Expr>, Box<Expr>),
+    /// A call to an intrinsic function; the name is upper case.
+    Call(String, Vec<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// A number and whether it is an integer literal.
+    Number(f64, bool),
+    Name(String),
+    Symbol(char),
+    Power,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, ParseError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        let c = chars[index];
+
+        if c.is_whitespace() {
+            index += 1;
+        } else if c.is_ascii_digit()
+            || (c == '.' && chars.get(index + 1).is_some_and(|c| c.is_ascii_digit()))
+        {
+            let start = index;
+            let mut integer = true;
+            while index < chars.len() && chars[index].is_ascii_digit() {
+                index += 1;
+            }
+            if chars.get(index) == Some(&'.') {
+                integer = false;
+                index += 1;
+                while index < chars.len() && chars[index].is_ascii_digit() {
+                    index += 1;
+                }
+            }
+            if chars
+                .get(index)
+                .is_some_and(|c| matches!(c, 'E' | 'e' | 'D' | 'd'))
+            {
+                let mut end = index + 1;
+                if chars.get(end).is_some_and(|c| matches!(c, '+' | '-')) {
+                    end += 1;
+                }
+                if chars.get(end).is_some_and(|c| c.is_ascii_digit()) {
+                    integer = false;
+                    index = end;
+                    while index < chars.len() && chars[index].is_ascii_digit() {
+                        index += 1;
+                    }
+                }
+            }
+
+            let text: String = chars[start..index].iter().collect();
+            let value = text
+                .replace(['D', 'd'], "E")
+                .parse::<f64>()
+                .map_err(|_| ParseError {
+                    message: format!("Failed to parse number: {}", text),
+                })?;
+            tokens.push(Token::Number(value, integer));
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let start = index;
+            while index < chars.len()
+                && (chars[index].is_ascii_alphanumeric() || chars[index] == '_')
+            {
+                index += 1;
+            }
+            let name: String = chars[start..index].iter().collect();
+            tokens.push(Token::Name(name.to_ascii_uppercase()));
+        } else if c == '*' && chars.get(index + 1) == Some(&'*') {
+            tokens.push(Token::Power);
+            index += 2;
+        } else if matches!(c, '+' | '-' | '*' | '/' | '(' | ')' | ',') {
+            tokens.push(Token::Symbol(c));
+            index += 1;
+        } else {
+            return Err(ParseError {
+                message: format!(
+                    "Unexpected character '{}' in expression: {}",
+                    c,
+                    input.trim()
+                ),
+            });
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive descent parser over the tokens of one expression. Every method
+/// returns the parsed expression and whether it has integer type.
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    position: usize,
+    integers: &'a HashSet<String>,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn eat(&mut self, symbol: char) -> bool {
+        if self.peek() == Some(&Token::Symbol(symbol)) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn error(&self) -> ParseError {
+        ParseError {
+            message: match self.peek() {
+                Some(token) => format!("Unexpected token in expression: {:?}", token),
+                None => "Unexpected end of expression".to_string(),
+            },
+        }
+    }
+
+    /// `[sign] term { (+|-) term }`
+    fn expression(&mut self) -> Result<(Expr, bool), ParseError> {
+        let mut left = self.signed(Self::term)?;
+
+        loop {
+            let op = if self.eat('+') {
+                Op::Add
+            } else if self.eat('-') {
+                Op::Sub
+            } else {
+                return Ok(left);
+            };
+            let right = self.term()?;
+            left = (
+                Expr::Binary(op, Box::new(left.0), Box::new(right.0)),
+                left.1 && right.1,
+            );
+        }
+    }
+
+    /// Parses an operand with an optional leading sign.
+    fn signed(
+        &mut self,
+        operand: fn(&mut Self) -> Result<(Expr, bool), ParseError>,
+    ) -> Result<(Expr, bool), ParseError> {
+        if self.eat('-') {
+            let (expr, integer) = operand(self)?;
+            Ok((Expr::Neg(Box::new(expr)), integer))
+        } else {
+            self.eat('+');
+            operand(self)
+        }
+    }
+
+    /// `factor { (*|/) factor }`
+    fn term(&mut self) -> Result<(Expr, bool), ParseError> {
+        let mut left = self.factor()?;
+
+        loop {
+            let multiply = if self.eat('*') {
+                true
+            } else if self.eat('/') {
+                false
+            } else {
+                return Ok(left);
+            };
+            let right = self.factor()?;
+            let integer = left.1 && right.1;
+            let op = match (multiply, integer) {
+                (true, _) => Op::Mul,
+                (false, true) => Op::IntDiv,
+                (false, false) => Op::Div,
+            };
+            left = (
+                Expr::Binary(op, Box::new(left.0), Box::new(right.0)),
+                integer,
+            );
+        }
+    }
+
+    /// `primary [** [sign] factor]`
+    fn factor(&mut self) -> Result<(Expr, bool), ParseError> {
+        let base = self.primary()?;
+
+        if self.peek() == Some(&Token::Power) {
+            self.position += 1;
+            let exponent = self.signed(Self::factor)?;
+            Ok((
+                Expr::Binary(Op::Pow, Box::new(base.0), Box::new(exponent.0)),
+                base.1 && exponent.1,
+            ))
+        } else {
+            Ok(base)
+        }
+    }
+
+    /// A number, a name, a function call or a parenthesised expression.
+    fn primary(&mut self) -> Result<(Expr, bool), ParseError> {
+        match self.peek().cloned() {
+            Some(Token::Number(value, integer)) => {
+                self.position += 1;
+                Ok((Expr::Number(value), integer))
+            }
+            Some(Token::Name(name)) => {
+                self.position += 1;
+                if !self.eat('(') {
+                    let integer = self.integers.contains(&name);
+                    return Ok((Expr::Name(name), integer));
+                }
+
+                let mut args = Vec::new();
+                loop {
+                    args.push(self.expression()?);
+                    if self.eat(')') {
+                        break;
+                    }
+                    if !self.eat(',') {
+                        return Err(self.error());
+                    }
+                }
+
+                let integer = function_type(&name, args.len(), args.iter().all(|arg| arg.1))?;
+                Ok((
+                    Expr::Call(name, args.into_iter().map(|arg| arg.0).collect()),
+                    integer,
+                ))
+            }
+            Some(Token::Symbol('(')) => {
+                self.position += 1;
+                let inner = self.expression()?;
+                if !self.eat(')') {
+                    return Err(self.error());
+                }
+                Ok(inner)
+            }
+            _ => Err(self.error()),
+        }
+    }
+}
+
+/// Checks a call to an intrinsic function and returns whether its result is
+/// an integer.
+fn function_type(name: &str, args: usize, integer_args: bool) -> Result<bool, ParseError> {
+    let (min_args, max_args, integer) = match name {
+        "SIN" | "DSIN" | "COS" | "DCOS" | "TAN" | "DTAN" | "ASIN" | "DASIN" | "ACOS" | "DACOS"
+        | "ATAN" | "DATAN" | "SINH" | "DSINH" | "COSH" | "DCOSH" | "TANH" | "DTANH" | "EXP"
+        | "DEXP" | "LOG" | "DLOG" | "ALOG" | "LOG10" | "DLOG10" | "ALOG10" | "SQRT" | "DSQRT"
+        | "DBLE" | "DFLOAT" | "FLOAT" | "REAL" | "SNGL" | "AINT" | "DINT" | "ANINT" | "DNINT" => {
+            (1, 1, false)
+        }
+        "INT" | "IDINT" | "IFIX" | "NINT" | "IDNINT" => (1, 1, true),
+        "ABS" | "DABS" | "IABS" => (1, 1, integer_args),
+        "ATAN2" | "DATAN2" => (2, 2, false),
+        "MOD" | "DMOD" | "AMOD" | "SIGN" | "DSIGN" | "ISIGN" => (2, 2, integer_args),
+        "MAX" | "MIN" | "DMAX1" | "DMIN1" | "AMAX1" | "AMIN1" | "MAX0" | "MIN0" => {
+            (2, usize::MAX, integer_args)
+        }
+        _ => {
+            return Err(ParseError {
+                message: format!("Unknown function: {}", name),
+            });
+        }
+    };
+
+    if args < min_args || args > max_args {
+        return Err(ParseError {
+            message: format!("Wrong number of arguments for function: {}", name),
+        });
+    }
+
+    Ok(integer)
+}
+
+impl Expr {
+    /// Parses a Fortran expression. Names in `integers` are integer
+    /// temporaries, which makes divisions by and of them integer divisions.
+    pub(crate) fn parse(input: &str, integers: &HashSet<String>) -> Result<Expr, ParseError> {
+        let mut parser = Parser {
+            tokens: tokenize(input)?,
+            position: 0,
+            integers,
+        };
+
+        let (expr, _) = parser.expression()?;
+        if parser.position < parser.tokens.len() {
+            return Err(parser.error());
+        }
+
+        Ok(expr)
+    }
+
+    /// Evaluates the expression. Names missing from `scope` evaluate to NaN.
+    pub(crate) fn evaluate(&self, scope: &HashMap<String, f64>) -> f64 {
+        match self {
+            Expr::Number(value) => *value,
+            Expr::Name(name) => scope.get(name).copied().unwrap_or(f64::NAN),
+            Expr::Neg(expr) => -expr.evaluate(scope),
+            Expr::Binary(op, left, right) => {
+                let (left, right) = (left.evaluate(scope), right.evaluate(scope));
+                match op {
+                    Op::Add => left + right,
+                    Op::Sub => left - right,
+                    Op::Mul => left * right,
+                    Op::Div => left / right,
+                    Op::IntDiv => (left / right).trunc(),
+                    Op::Pow => {
+                        if right.fract() == 0.0 && right.abs() <= i32::MAX as f64 {
+                            left.powi(right as i32)
+                        } else {
+                            left.powf(right)
+                        }
+                    }
+                }
+            }
+            Expr::Call(name, args) => {
+                let args: Vec<f64> = args.iter().map(|arg| arg.evaluate(scope)).collect();
+                call(name, &args)
+            }
+        }
+    }
+
+    /// Adds the names the expression refers to to `names`.
+    pub(crate) fn names(&self, names: &mut BTreeSet<String>) {
+        match self {
+            Expr::Number(_) => {}
+            Expr::Name(name) => {
+                names.insert(name.clone());
+            }
+            Expr::Neg(expr) => expr.names(names),
+            Expr::Binary(_, left, right) => {
+                left.names(names);
+                right.names(names);
+            }
+            Expr::Call(_, args) => args.iter().for_each(|arg| arg.names(names)),
+        }
+    }
+}
+
+/// Evaluates an intrinsic function already checked by [`function_type`].
+fn call(name: &str, args: &[f64]) -> f64 {
+    let x = args[0];
+    match name {
+        "SIN" | "DSIN" => x.sin(),
+        "COS" | "DCOS" => x.cos(),
+        "TAN" | "DTAN" => x.tan(),
+        "ASIN" | "DASIN" => x.asin(),
+        "ACOS" | "DACOS" => x.acos(),
+        "ATAN" | "DATAN" => x.atan(),
+        "SINH" | "DSINH" => x.sinh(),
+        "COSH" | "DCOSH" => x.cosh(),
+        "TANH" | "DTANH" => x.tanh(),
+        "EXP" | "DEXP" => x.exp(),
+        "LOG" | "DLOG" | "ALOG" => x.ln(),
+        "LOG10" | "DLOG10" | "ALOG10" => x.log10(),
+        "SQRT" | "DSQRT" => x.sqrt(),
+        "ABS" | "DABS" | "IABS" => x.abs(),
+        "INT" | "IDINT" | "IFIX" | "AINT" | "DINT" => x.trunc(),
+        "NINT" | "IDNINT" | "ANINT" | "DNINT" => x.round(),
+        "ATAN2" | "DATAN2" => x.atan2(args[1]),
+        "MOD" | "DMOD" | "AMOD" => x % args[1],
+        "SIGN" | "DSIGN" | "ISIGN" => x.abs().copysign(args[1]),
+        "MAX" | "DMAX1" | "AMAX1" | "MAX0" => {
+            args.iter().copied().fold(f64::NEG_INFINITY, f64::max)
+        }
+        "MIN" | "DMIN1" | "AMIN1" | "MIN0" => args.iter().copied().fold(f64::INFINITY, f64::min),
+        _ => x,
+    }
+}
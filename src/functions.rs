@@ -0,0 +1,564 @@
+//! Element and group functions defined in the `ELEMENTS` and `GROUPS`
+//! sections that follow the first `ENDATA` of a SIF file.
+//!
+//! Both sections are made of up to three parts:
+//!
+//! ```text
+//!  TEMPORARIES          declarations of temporaries (R, I, L, M, F cards)
+//!  GLOBALS              assignments shared by all types (A)
+//!  INDIVIDUALS          one block per element or group type, started by a T card
+//! ```
+//!
+//! Each block may give assignments to temporaries (`A`), the function value
+//! (`F`), its first derivatives (`G`) and its second derivatives (`H`);
+//! element blocks may also give a range transformation (`R` cards).
+//! Expressions are Fortran, with case-insensitive names, and start in field 7
+//! (column 25); a code ending with `+` continues the expression of the
+//! previous card. The conditional assignments `I` and `E`, which depend on a
+//! logical temporary, are rejected since logical expressions are not
+//! supported.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::{
+    ParseError, card_code, check_length,
+    expr::Expr,
+    parse_value,
+    types::{ElementType, GroupType},
+};
+
+/// The function of a nonlinear element type, from the `ELEMENTS` section.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementFunction {
+    name: String,
+    elemental_variables: Vec<String>,
+    internal_variables: Vec<String>,
+    parameters: Vec<String>,
+    /// Range transformation: row `i` gives internal variable `i` as a linear
+    /// combination of the elemental variables.
+    transformation: Vec<Vec<f64>>,
+    /// Assignments to temporaries in evaluation order, globals first.
+    assignments: Vec<(String, Expr)>,
+    value: Option<Expr>,
+    /// Gradient entries keyed by the index of the (internal) variable.
+    gradient: Vec<(usize, Expr)>,
+    /// Hessian entries keyed by the indices of the (internal) variables.
+    hessian: Vec<(usize, usize, Expr)>,
+}
+
+impl ElementFunction {
+    /// Returns the name of the element type the function belongs to.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the range transformation, one row per internal variable and
+    /// one column per elemental variable; empty when the element has none.
+    pub fn get_transformation(&self) -> &Vec<Vec<f64>> {
+        &self.transformation
+    }
+
+    /// Evaluates the element function, its gradient and its Hessian.
+    ///
+    /// `variables` and `parameters` are given in the order of the elemental
+    /// variables and parameters of the [`ElementType`]. Derivatives are taken
+    /// with respect to the elemental variables; when the element has a range
+    /// transformation `u = W v`, the derivatives given in the file are with
+    /// respect to `u` and are mapped back as `Wᵀ g` and `Wᵀ H W`. Entries the
+    /// file does not give are zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if `variables` or `parameters` do not match
+    /// the element type.
+    #[allow(clippy::type_complexity)]
+    pub fn evaluate(
+        &self,
+        variables: &[f64],
+        parameters: &[f64],
+    ) -> Result<(f64, Vec<f64>, Vec<Vec<f64>>), ParseError> {
+        check_length(
+            "elemental variables",
+            variables,
+            self.elemental_variables.len(),
+        )?;
+        check_length("element parameters", parameters, self.parameters.len())?;
+
+        let mut scope: HashMap<String, f64> = HashMap::new();
+        for (name, value) in self.elemental_variables.iter().zip(variables) {
+            scope.insert(name.clone(), *value);
+        }
+        for (row, name) in self.transformation.iter().zip(&self.internal_variables) {
+            let value = row.iter().zip(variables).map(|(w, v)| w * v).sum();
+            scope.insert(name.clone(), value);
+        }
+        for (name, value) in self.parameters.iter().zip(parameters) {
+            scope.insert(name.clone(), *value);
+        }
+        for (name, expr) in &self.assignments {
+            let value = expr.evaluate(&scope);
+            scope.insert(name.clone(), value);
+        }
+
+        let n = self.derivative_variables().len();
+        let value = self
+            .value
+            .as_ref()
+            .map_or(0.0, |expr| expr.evaluate(&scope));
+        let mut gradient = vec![0.0; n];
+        for (i, expr) in &self.gradient {
+            gradient[*i] = expr.evaluate(&scope);
+        }
+        let mut hessian = vec![vec![0.0; n]; n];
+        for (i, j, expr) in &self.hessian {
+            let entry = expr.evaluate(&scope);
+            hessian[*i][*j] = entry;
+            hessian[*j][*i] = entry;
+        }
+
+        if self.transformation.is_empty() {
+            return Ok((value, gradient, hessian));
+        }
+
+        let w = &self.transformation;
+        let m = variables.len();
+        let gradient = (0..m)
+            .map(|k| (0..n).map(|i| w[i][k] * gradient[i]).sum())
+            .collect();
+        let hessian = (0..m)
+            .map(|k| {
+                (0..m)
+                    .map(|l| {
+                        (0..n)
+                            .map(|i| {
+                                (0..n)
+                                    .map(|j| w[i][k] * hessian[i][j] * w[j][l])
+                                    .sum::<f64>()
+                            })
+                            .sum()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Ok((value, gradient, hessian))
+    }
+
+    /// The variables the derivatives in the file refer to: the internal
+    /// variables when there is a range transformation, otherwise the
+    /// elemental variables.
+    fn derivative_variables(&self) -> &Vec<String> {
+        if self.transformation.is_empty() {
+            &self.elemental_variables
+        } else {
+            &self.internal_variables
+        }
+    }
+}
+
+/// The function of a nonlinear group type, from the `GROUPS` section.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupFunction {
+    name: String,
+    group_variable: String,
+    parameters: Vec<String>,
+    /// Assignments to temporaries in evaluation order, globals first.
+    assignments: Vec<(String, Expr)>,
+    value: Option<Expr>,
+    derivative: Option<Expr>,
+    second_derivative: Option<Expr>,
+}
+
+impl GroupFunction {
+    /// Returns the name of the group type the function belongs to.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Evaluates the group function g and its first and second derivatives
+    /// g' and g'' at `alpha`.
+    ///
+    /// `parameters` are given in the order of the parameters of the
+    /// [`GroupType`]. Derivatives the file does not give are zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if `parameters` do not match the group type.
+    pub fn evaluate(&self, alpha: f64, parameters: &[f64]) -> Result<(f64, f64, f64), ParseError> {
+        check_length("group parameters", parameters, self.parameters.len())?;
+
+        let mut scope: HashMap<String, f64> = HashMap::new();
+        scope.insert(self.group_variable.clone(), alpha);
+        for (name, value) in self.parameters.iter().zip(parameters) {
+            scope.insert(name.clone(), *value);
+        }
+        for (name, expr) in &self.assignments {
+            let value = expr.evaluate(&scope);
+            scope.insert(name.clone(), value);
+        }
+
+        let evaluate =
+            |expr: &Option<Expr>| expr.as_ref().map_or(0.0, |expr| expr.evaluate(&scope));
+        Ok((
+            evaluate(&self.value),
+            evaluate(&self.derivative),
+            evaluate(&self.second_derivative),
+        ))
+    }
+}
+
+/// A card of a function section: the code without continuation marker, the
+/// names in fields 2 and 3, and the expression or remaining fields.
+type Card = (String, Vec<String>, String);
+
+/// Splits a card, after its code, into `names` leading names and the
+/// remaining text.
+fn split_card(line: &str, names: usize) -> (Vec<String>, String) {
+    let mut rest = line.get(3..).unwrap_or("").trim_start();
+    let mut fields = Vec::new();
+
+    for _ in 0..names {
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        fields.push(rest[..end].to_string());
+        rest = rest[end..].trim_start();
+    }
+
+    (fields, rest.trim_end().to_string())
+}
+
+/// Number of names that precede the expression or values on a card of an
+/// element block, or `None` for codes that are not allowed there.
+fn element_card_names(code: &str) -> Option<usize> {
+    match code {
+        "R" | "F" => Some(0),
+        "A" | "G" => Some(1),
+        "H" => Some(2),
+        _ => None,
+    }
+}
+
+/// Number of names that precede the expression on a card of a group block,
+/// or `None` for codes that are not allowed there. Group functions have a
+/// single variable, so derivative cards name none.
+fn group_card_names(code: &str) -> Option<usize> {
+    match code {
+        "F" | "G" | "H" => Some(0),
+        "A" => Some(1),
+        _ => None,
+    }
+}
+
+/// The parts of a function section.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Part {
+    Temporaries,
+    Globals,
+    Individuals,
+}
+
+/// Reads the cards of a function section into the temporaries declared as
+/// integers, the global cards and the cards of each individual block.
+/// `card_names` gives the number of names on each kind of individual card.
+#[allow(clippy::type_complexity)]
+fn read_cards(
+    input: &str,
+    card_names: fn(&str) -> Option<usize>,
+) -> Result<(HashSet<String>, Vec<Card>, Vec<(String, Vec<Card>)>), ParseError> {
+    let mut part = Part::Individuals;
+    let mut integers = HashSet::new();
+    let mut globals: Vec<Card> = Vec::new();
+    let mut individuals: Vec<(String, Vec<Card>)> = Vec::new();
+
+    for line in input.lines() {
+        let header = match line.trim() {
+            "TEMPORARIES" => Some(Part::Temporaries),
+            "GLOBALS" => Some(Part::Globals),
+            "INDIVIDUALS" => Some(Part::Individuals),
+            _ => None,
+        };
+        if let Some(header) = header {
+            part = header;
+            continue;
+        }
+
+        let code = card_code(line).ok_or_else(|| ParseError {
+            message: format!("Malformed function entry: {}", line.trim()),
+        })?;
+        let (base, continued) = match code.strip_suffix('+') {
+            Some(base) => (base, true),
+            None => (code, false),
+        };
+
+        if part == Part::Temporaries {
+            let (names, _) = split_card(line, 1);
+            match code {
+                "I" => {
+                    integers.insert(names[0].to_ascii_uppercase());
+                }
+                "R" | "L" | "M" | "F" => {}
+                _ => {
+                    return Err(ParseError {
+                        message: format!("Unknown temporaries entry: {}", code),
+                    });
+                }
+            }
+            continue;
+        }
+
+        let cards = if part == Part::Globals {
+            &mut globals
+        } else if base == "T" {
+            let (names, _) = split_card(line, 1);
+            individuals.push((names[0].clone(), Vec::new()));
+            continue;
+        } else {
+            &mut individuals
+                .last_mut()
+                .ok_or_else(|| ParseError {
+                    message: format!("Function entry before any T entry: {}", line.trim()),
+                })?
+                .1
+        };
+
+        if continued {
+            match cards.last_mut() {
+                Some((previous, _, text)) if *previous == base => {
+                    *text += " ";
+                    *text += split_card(line, 0).1.as_str();
+                }
+                _ => {
+                    return Err(ParseError {
+                        message: format!("Continuation without a preceding {} entry", base),
+                    });
+                }
+            }
+            continue;
+        }
+
+        let names = match (part, base, card_names(base)) {
+            (_, "I" | "E", _) => {
+                return Err(ParseError {
+                    message: format!("Conditional assignments are not supported: {}", code),
+                });
+            }
+            (Part::Globals, "A", _) => 1,
+            (Part::Individuals, _, Some(names)) => names,
+            _ => {
+                return Err(ParseError {
+                    message: format!("Unknown function entry: {}", code),
+                });
+            }
+        };
+        let (names, text) = split_card(line, names);
+        cards.push((base.to_string(), names, text));
+    }
+
+    Ok((integers, globals, individuals))
+}
+
+/// Returns `names` in upper case, the case expressions refer to them in.
+fn upper_case(names: &[String]) -> Vec<String> {
+    names.iter().map(|name| name.to_ascii_uppercase()).collect()
+}
+
+/// Parses global assignment cards into `(name, expression)` pairs.
+fn parse_assignments(
+    cards: &[Card],
+    integers: &HashSet<String>,
+) -> Result<Vec<(String, Expr)>, ParseError> {
+    cards
+        .iter()
+        .map(|(_, names, text)| Ok((names[0].to_ascii_uppercase(), Expr::parse(text, integers)?)))
+        .collect()
+}
+
+/// Returns an error naming the first name used by the expressions that is
+/// not in `known`.
+fn check_names<'a>(
+    function: &str,
+    exprs: impl Iterator<Item = &'a Expr>,
+    known: &HashSet<String>,
+) -> Result<(), ParseError> {
+    let mut names = BTreeSet::new();
+    exprs.for_each(|expr| expr.names(&mut names));
+
+    match names.iter().find(|name| !known.contains(*name)) {
+        Some(name) => Err(ParseError {
+            message: format!("Function {} references undefined name: {}", function, name),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Parses the body of an `ELEMENTS` section into one function per element
+/// type, checking it against the element types declared in the data part.
+pub(crate) fn parse_element_functions(
+    input: &str,
+    element_types: &[ElementType],
+) -> Result<Vec<ElementFunction>, ParseError> {
+    let (integers, globals, individuals) = read_cards(input, element_card_names)?;
+    let globals = parse_assignments(&globals, &integers)?;
+
+    let mut functions = Vec::new();
+    for (name, cards) in individuals {
+        let element_type = element_types
+            .iter()
+            .find(|element_type| element_type.name == name)
+            .ok_or_else(|| ParseError {
+                message: format!(
+                    "Element function references undefined element type: {}",
+                    name
+                ),
+            })?;
+
+        let mut function = ElementFunction {
+            name: name.clone(),
+            elemental_variables: upper_case(&element_type.elemental_variables),
+            internal_variables: upper_case(&element_type.internal_variables),
+            parameters: upper_case(&element_type.parameters),
+            transformation: Vec::new(),
+            assignments: globals.clone(),
+            value: None,
+            gradient: Vec::new(),
+            hessian: Vec::new(),
+        };
+
+        let m = function.elemental_variables.len();
+        if !function.internal_variables.is_empty() {
+            function.transformation = vec![vec![0.0; m]; function.internal_variables.len()];
+        }
+
+        let index = |variables: &Vec<String>, variable: &str| -> Result<usize, ParseError> {
+            variables
+                .iter()
+                .position(|v| v.eq_ignore_ascii_case(variable))
+                .ok_or_else(|| ParseError {
+                    message: format!(
+                        "Function {} references undefined variable: {}",
+                        name, variable
+                    ),
+                })
+        };
+
+        for (code, names, text) in &cards {
+            match code.as_str() {
+                "R" => {
+                    let fields: Vec<&str> = text.split_whitespace().collect();
+                    let i = index(
+                        &function.internal_variables,
+                        fields.first().copied().unwrap_or(""),
+                    )?;
+                    for pair in fields.get(1..).unwrap_or_default().chunks(2) {
+                        let k = index(&function.elemental_variables, pair[0])?;
+                        function.transformation[i][k] +=
+                            parse_value(pair.get(1).copied().unwrap_or(""))?;
+                    }
+                }
+                "A" => {
+                    function
+                        .assignments
+                        .push((names[0].to_ascii_uppercase(), Expr::parse(text, &integers)?));
+                }
+                "F" => function.value = Some(Expr::parse(text, &integers)?),
+                "G" => {
+                    let i = index(function.derivative_variables(), &names[0])?;
+                    function.gradient.push((i, Expr::parse(text, &integers)?));
+                }
+                "H" => {
+                    let i = index(function.derivative_variables(), &names[0])?;
+                    let j = index(function.derivative_variables(), &names[1])?;
+                    function.hessian.push((i, j, Expr::parse(text, &integers)?));
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        let known: HashSet<String> = function
+            .elemental_variables
+            .iter()
+            .chain(&function.internal_variables)
+            .chain(&function.parameters)
+            .chain(function.assignments.iter().map(|(name, _)| name))
+            .cloned()
+            .collect();
+        check_names(
+            &name,
+            function
+                .assignments
+                .iter()
+                .map(|(_, expr)| expr)
+                .chain(&function.value)
+                .chain(function.gradient.iter().map(|(_, expr)| expr))
+                .chain(function.hessian.iter().map(|(_, _, expr)| expr)),
+            &known,
+        )?;
+
+        functions.push(function);
+    }
+
+    Ok(functions)
+}
+
+/// Parses the body of a `GROUPS` function section into one function per
+/// group type, checking it against the group types declared in the data part.
+pub(crate) fn parse_group_functions(
+    input: &str,
+    group_types: &[GroupType],
+) -> Result<Vec<GroupFunction>, ParseError> {
+    let (integers, globals, individuals) = read_cards(input, group_card_names)?;
+    let globals = parse_assignments(&globals, &integers)?;
+
+    let mut functions = Vec::new();
+    for (name, cards) in individuals {
+        let group_type = group_types
+            .iter()
+            .find(|group_type| group_type.name == name)
+            .ok_or_else(|| ParseError {
+                message: format!("Group function references undefined group type: {}", name),
+            })?;
+
+        let mut function = GroupFunction {
+            name: name.clone(),
+            group_variable: group_type.group_variable.to_ascii_uppercase(),
+            parameters: upper_case(&group_type.parameters),
+            assignments: globals.clone(),
+            value: None,
+            derivative: None,
+            second_derivative: None,
+        };
+
+        for (code, names, text) in &cards {
+            let expr = Expr::parse(text, &integers)?;
+            match code.as_str() {
+                "A" => function
+                    .assignments
+                    .push((names[0].to_ascii_uppercase(), expr)),
+                "F" => function.value = Some(expr),
+                "G" => function.derivative = Some(expr),
+                "H" => function.second_derivative = Some(expr),
+                _ => unreachable!(),
+            }
+        }
+
+        let known: HashSet<String> = std::iter::once(&function.group_variable)
+            .chain(&function.parameters)
+            .chain(function.assignments.iter().map(|(name, _)| name))
+            .cloned()
+            .collect();
+        check_names(
+            &name,
+            function
+                .assignments
+                .iter()
+                .map(|(_, expr)| expr)
+                .chain(&function.value)
+                .chain(&function.derivative)
+                .chain(&function.second_derivative),
+            &known,
+        )?;
+
+        functions.push(function);
+    }
+
+    Ok(functions)
+}
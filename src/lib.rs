@@ -25,18 +25,25 @@
 //! let input = std::fs::read_to_string("examples/qptest.sif").unwrap();
 //! let sif = sif_rs::parse_sif(&input).unwrap();
 //! ```
+pub mod evaluator;
+mod expr;
+pub mod functions;
 pub mod types;
 
 use derive_more::Display;
 use regex::Regex;
 use std::{
-    collections::{BTreeMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     error::Error,
     str::FromStr,
     sync::LazyLock,
 };
 
-use types::{ColumnType, Indicator, Major, RowType};
+use functions::{ElementFunction, GroupFunction};
+use types::{
+    ColumnType, CscMatrix, CsrMatrix, ElementType, ElementUse, GroupType, GroupUse, Indicator,
+    Major, ObjSense, RowType,
+};
 
 use crate::types::BoundType;
 
@@ -48,8 +55,162 @@ pub struct ParseError {
 
 impl Error for ParseError {}
 
-static RE_CARDS: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"(?m)(^[A-Z]+)\n((^[ \t]+.*\n)+)").unwrap());
+/// Matches a section: the indicator (possibly two words, e.g. `START POINT`),
+/// an optional trailing field on the indicator line, and the indented body,
+/// which is empty for cards such as `OBJSENSE MAX` that hold their value on
+/// the indicator line.
+static RE_CARDS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^([A-Z]+(?: [A-Z]+)?'?)(?:[ \t]+(\S.*?))?[ \t]*\n((?:^[ \t]+.*\n)*)").unwrap()
+});
+
+/// Matches the `ENDATA` card that closes the data part of a file; the
+/// function sections (`ELEMENTS`, `GROUPS`) may follow it.
+static RE_ENDATA: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?m)^ENDATA\b.*\n?").unwrap());
+
+/// Removes comment lines (starting with `*`), trailing `$` comments and blank
+/// lines so that every section body is a contiguous block of data rows.
+fn strip_comments(input: &str) -> String {
+    input
+        .lines()
+        .map(|line| match line.find(" $") {
+            Some(index) => line[..index].trim_end(),
+            None => line,
+        })
+        .filter(|line| !line.starts_with('*') && !line.trim().is_empty())
+        .map(|line| line.to_string() + "\n")
+        .collect()
+}
+
+/// Returns the code in field 1 (columns 2-3) of a SIF card, if any.
+fn card_code(line: &str) -> Option<&str> {
+    let code = line.get(1..3)?;
+    let separated = line.get(3..4).is_none_or(|c| c == " " || c == "\t");
+
+    (line.starts_with(' ') && !code.starts_with(char::is_whitespace) && separated)
+        .then(|| code.trim_end())
+}
+
+/// Returns the indicator of a section matched by [`RE_CARDS`] and the field
+/// that may follow it on the indicator line. A two-word match that is not an
+/// indicator, such as `OBJSENSE MAX` or `ELEMENTS PROBLEM`, is an indicator
+/// followed by its field after a single space.
+fn split_indicator<'a>(
+    card: &'a regex::Captures,
+) -> Result<(Indicator, Option<&'a str>), ParseError> {
+    let field = card.get(2).map(|field| field.as_str());
+    if let Ok(indicator) = Indicator::from_str(&card[1]) {
+        return Ok((indicator, field));
+    }
+
+    match card[1].split_once(' ') {
+        Some((indicator, value)) => Ok((Indicator::from_str(indicator)?, Some(value))),
+        None => Err(ParseError {
+            message: format!("Unknown indicator: {}", &card[1]),
+        }),
+    }
+}
+
+/// Returns `true` for the codes of parameter definition cards, such as `IE`,
+/// `RM` or `A+`.
+fn is_parameter_code(code: &str) -> bool {
+    let mut chars = code.chars();
+    matches!(
+        (chars.next(), chars.next(), chars.next()),
+        (
+            Some('I' | 'R' | 'A'),
+            Some('E' | 'I' | 'R' | 'A' | 'S' | 'M' | 'D' | 'F' | '(' | '=' | '+' | '-' | '*' | '/'),
+            None
+        )
+    )
+}
+
+/// Finds the end of the loop whose body starts at `start`.
+///
+/// Returns the index one past the last card of the body and the index of the
+/// first card after the loop. The body ends at the matching `OD`, at an `ND`
+/// (which closes all loops) or at the end of `lines`, as happens for loops
+/// nested in a block that an `ND` terminated.
+fn find_loop_end(lines: &[&str], start: usize) -> (usize, usize) {
+    let mut depth = 0;
+
+    for (index, line) in lines.iter().enumerate().skip(start) {
+        match card_code(line) {
+            Some("DO") => depth += 1,
+            Some("OD") if depth == 0 => return (index, index + 1),
+            Some("OD") => depth -= 1,
+            Some("ND") => return (index, index + 1),
+            _ => {}
+        }
+    }
+
+    (lines.len(), lines.len())
+}
+
+/// Returns the increment given by a `DI` card for `variable` directly inside
+/// a loop body, if any.
+fn find_loop_step<'a>(body: &[&'a str], variable: &str) -> Option<&'a str> {
+    let mut depth = 0;
+
+    for line in body {
+        let mut fields = line.get(3..).unwrap_or("").split_whitespace();
+        match card_code(line) {
+            Some("DO") => depth += 1,
+            Some("OD") => depth -= 1,
+            Some("DI") if depth == 0 && fields.next() == Some(variable) => return fields.next(),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Evaluates a SIF intrinsic function, as used by `RF` and `R(` cards.
+fn intrinsic(name: &str, x: f64) -> Result<f64, ParseError> {
+    let value = match name {
+        "ABS" => x.abs(),
+        "SQRT" => x.sqrt(),
+        "EXP" => x.exp(),
+        "LOG" => x.ln(),
+        "LOG10" => x.log10(),
+        "SIN" => x.sin(),
+        "COS" => x.cos(),
+        "TAN" => x.tan(),
+        "ARCSIN" => x.asin(),
+        "ARCCOS" => x.acos(),
+        "ARCTAN" => x.atan(),
+        "HYPSIN" => x.sinh(),
+        "HYPCOS" => x.cosh(),
+        "HYPTAN" => x.tanh(),
+        _ => {
+            return Err(ParseError {
+                message: format!("Unknown intrinsic function: {}", name),
+            });
+        }
+    };
+
+    Ok(value)
+}
+
+/// Rewrites a Fortran-style number such as `1.0D+2` with an `E` exponent so
+/// that it can be parsed as `f64`. Other tokens are returned unchanged.
+fn fortran_number(token: &str) -> String {
+    static RE_FORTRAN: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"^[+-]?(\d+\.?\d*|\.\d+)[Dd][+-]?\d+$").unwrap());
+
+    if RE_FORTRAN.is_match(token) {
+        token.replace(['D', 'd'], "E")
+    } else {
+        token.to_string()
+    }
+}
+
+/// Parses a single field, falling back to reading Fortran-style numbers such
+/// as `1.0D+2`.
+fn parse_field<F: FromStr>(field: &str) -> Result<F, F::Err> {
+    field
+        .parse::<F>()
+        .or_else(|err| fortran_number(field).parse::<F>().map_err(|_| err))
+}
 
 /// Parses a single SIF data row into five typed fields.
 ///
@@ -76,75 +237,42 @@ fn parse_sif_row<
 >(
     input: &str,
 ) -> Result<(F1, F2, F3, F4, F5), ParseError> {
-    let input = {
-        if input.chars().next() == Some(' ') {
-            "a".to_owned() + &input[1..]
-        } else {
-            input.to_string()
-        }
+    let input = match input.strip_prefix(' ') {
+        Some(rest) => "a".to_owned() + rest,
+        None => input.to_string(),
     };
 
     let split_input = input.split_whitespace();
     let fields: Vec<&str> = split_input.collect();
 
-    let f1 = fields
-        .get(0)
-        .unwrap_or(&"")
-        .trim()
-        .to_string()
-        .parse::<F1>()
-        .map_err(|_| ParseError {
-            message: "Failed to parse field 1".to_string(),
-        })?;
+    let f1 = parse_field::<F1>(fields.first().unwrap_or(&"").trim()).map_err(|_| ParseError {
+        message: "Failed to parse field 1".to_string(),
+    })?;
 
-    let f2 = fields
-        .get(1)
-        .unwrap_or(&"")
-        .trim()
-        .to_string()
-        .parse::<F2>()
-        .map_err(|_| ParseError {
-            message: "Failed to parse field 2".to_string(),
-        })?;
+    let f2 = parse_field::<F2>(fields.get(1).unwrap_or(&"").trim()).map_err(|_| ParseError {
+        message: "Failed to parse field 2".to_string(),
+    })?;
 
     let f3 = if fields.len() > 2 {
-        fields
-            .get(2)
-            .unwrap_or(&"")
-            .trim()
-            .to_string()
-            .parse::<F3>()
-            .map_err(|_| ParseError {
-                message: "Failed to parse field 3".to_string(),
-            })?
+        parse_field::<F3>(fields.get(2).unwrap_or(&"").trim()).map_err(|_| ParseError {
+            message: "Failed to parse field 3".to_string(),
+        })?
     } else {
         F3::default()
     };
 
     let f4 = if fields.len() > 3 {
-        fields
-            .get(3)
-            .unwrap_or(&"")
-            .trim()
-            .to_string()
-            .parse::<F4>()
-            .map_err(|_| ParseError {
-                message: "Failed to parse field 4".to_string(),
-            })?
+        parse_field::<F4>(fields.get(3).unwrap_or(&"").trim()).map_err(|_| ParseError {
+            message: "Failed to parse field 4".to_string(),
+        })?
     } else {
         F4::default()
     };
 
     let f5 = if fields.len() > 4 {
-        fields
-            .get(4)
-            .unwrap_or(&"")
-            .trim()
-            .to_string()
-            .parse::<F5>()
-            .map_err(|_| ParseError {
-                message: "Failed to parse field 5".to_string(),
-            })?
+        parse_field::<F5>(fields.get(4).unwrap_or(&"").trim()).map_err(|_| ParseError {
+            message: "Failed to parse field 5".to_string(),
+        })?
     } else {
         F5::default()
     };
@@ -152,6 +280,128 @@ fn parse_sif_row<
     Ok((f1, f2, f3, f4, f5))
 }
 
+/// Returns an error unless `values` holds exactly `expected` values of the
+/// kind named by `what`.
+fn check_length(what: &str, values: &[f64], expected: usize) -> Result<(), ParseError> {
+    if values.len() == expected {
+        return Ok(());
+    }
+
+    Err(ParseError {
+        message: format!("Expected {} {}, got {}", expected, what, values.len()),
+    })
+}
+
+/// Parses a numeric field, accepting Fortran-style `D` exponents such as
+/// `1.0D+2`. An empty field reads as zero.
+fn parse_value(field: &str) -> Result<f64, ParseError> {
+    if field.is_empty() {
+        return Ok(0.0);
+    }
+
+    field
+        .replace(['D', 'd'], "E")
+        .parse::<f64>()
+        .map_err(|_| ParseError {
+            message: format!("Failed to parse value: {}", field),
+        })
+}
+
+/// Returns the set name (first field) of an `RHS`, `RANGES` or `BOUNDS` row,
+/// mapping the placeholder produced by [`parse_sif_row`] for a blank field
+/// back to an empty name.
+fn set_name(row: &str, f1: String) -> String {
+    if row.starts_with(char::is_whitespace) {
+        String::new()
+    } else {
+        f1
+    }
+}
+
+/// Recognises an MPS integer marker row such as
+/// `MARKER 'MARKER' 'INTORG'`.
+///
+/// Returns `Some(true)` when an integer block starts (`'INTORG'`),
+/// `Some(false)` when it ends (`'INTEND'`) and `None` for any other row.
+fn parse_marker(row: &str) -> Result<Option<bool>, ParseError> {
+    if !row.contains("'MARKER'") {
+        return Ok(None);
+    }
+
+    if row.contains("'INTORG'") {
+        Ok(Some(true))
+    } else if row.contains("'INTEND'") {
+        Ok(Some(false))
+    } else {
+        Err(ParseError {
+            message: format!("Unknown marker: {}", row.trim()),
+        })
+    }
+}
+
+/// Groups `(set_name, key, value)` records into named sets, keeping the sets
+/// in order of first appearance.
+fn group_sets(records: &[(String, String, f64)]) -> Vec<(String, BTreeMap<String, f64>)> {
+    let mut sets: Vec<(String, BTreeMap<String, f64>)> = Vec::new();
+
+    for (set_name, key, value) in records {
+        match sets.iter_mut().find(|(name, _)| name == set_name) {
+            Some((_, set)) => {
+                set.insert(key.clone(), *value);
+            }
+            None => sets.push((set_name.clone(), BTreeMap::from([(key.clone(), *value)]))),
+        }
+    }
+
+    sets
+}
+
+/// Matches a `$-PARAMETER` definition, commented out or not: the text up to
+/// the value (with the comment marker and the parameter name as nested
+/// groups), the value and the trailing comment.
+static RE_PARAMETER_CHOICE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^((\*?)[ \t]*[IR]E[ \t]+(\S+)[ \t]+)(\S+)([ \t]+\$-PARAMETER.*)$").unwrap()
+});
+
+/// Collects the values offered for each `$-PARAMETER` in file order, and
+/// substitutes the values given in `options` into the active definitions.
+///
+/// Commented-out definitions are the documented alternatives; they are listed
+/// but never evaluated.
+fn select_parameters(
+    input: &str,
+    options: &ParseOptions,
+) -> Result<(String, BTreeMap<String, Vec<f64>>), ParseError> {
+    let mut choices: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+    for card in RE_PARAMETER_CHOICE.captures_iter(input) {
+        let values = choices.entry(card[3].to_string()).or_default();
+        let value = parse_value(&card[4])?;
+        if !values.contains(&value) {
+            values.push(value);
+        }
+    }
+
+    for name in options.parameters.keys() {
+        if !choices.contains_key(name) {
+            return Err(ParseError {
+                message: format!("Unknown $-PARAMETER: {}", name),
+            });
+        }
+    }
+
+    let output = RE_PARAMETER_CHOICE.replace_all(input, |card: &regex::Captures| {
+        match options.parameters.get(&card[3]) {
+            Some(value) if card[2].is_empty() => format!("{}{}{}", &card[1], value, &card[5]),
+            _ => card[0].to_string(),
+        }
+    });
+
+    Ok((output.into_owned(), choices))
+}
+
+/// A parsed `ROWS`/`GROUPS` card: `(row_name, row_type, [(col_name, value)])`.
+type GroupCard = (String, RowType, Vec<(String, f64)>);
+
 #[allow(dead_code)]
 struct SifParser {
     name: String,
@@ -164,9 +414,39 @@ struct SifParser {
     entries: Vec<(String, String, f64)>,
 
     rhs: Vec<(String, String, f64)>,
-    ranges: Vec<(String, f64)>,
+    ranges: Vec<(String, String, f64)>,
     bounds: Vec<(String, BoundType, String, f64)>,
+    start_point: Vec<(String, String, f64)>,
     quadratic: Vec<(String, String, f64)>,
+    /// Contribution of each quadratic term to the symmetric matrix Q of
+    /// `½ xᵀQx`: `(col_name_i, col_name_j, q)`, entries of a pair adding up.
+    quadratic_terms: Vec<(String, String, f64)>,
+    object_bounds: Vec<(String, BoundType, f64)>,
+    /// Objective sense from the `OBJSENSE` card.
+    objective_sense: ObjSense,
+    /// Objective row named by the `OBJNAME` card.
+    objective_name: Option<String>,
+
+    /// Parameter values defined so far.
+    parameters: BTreeMap<String, f64>,
+    /// Row scale factors given with `'SCALE'`: `(row_name, factor)`.
+    scales: Vec<(String, f64)>,
+    /// `D`-type rows: `(row_name, row_1, multiplier_1, row_2, multiplier_2)`.
+    combinations: Vec<(String, String, f64, String, f64)>,
+    /// Values offered for each `$-PARAMETER`, in file order.
+    parameter_choices: BTreeMap<String, Vec<f64>>,
+
+    element_types: Vec<ElementType>,
+    element_uses: Vec<ElementUse>,
+    /// Element type given to elements without a `T` card.
+    default_element_type: Option<String>,
+    group_types: Vec<GroupType>,
+    group_uses: Vec<GroupUse>,
+    /// Group type given to groups without a `T` card.
+    default_group_type: Option<String>,
+
+    element_functions: Vec<ElementFunction>,
+    group_functions: Vec<GroupFunction>,
 }
 
 impl SifParser {
@@ -207,29 +487,440 @@ impl SifParser {
                 .len() as i8,
         );
 
-        let mut rows = Vec::new();
-
         for row in input.lines() {
             let sep = self.sep.ok_or_else(|| ParseError {
                 message: "Separator not set before parsing entries".to_string(),
             })?;
             let type_str = row[..sep as usize].trim();
             let row = row[sep as usize..].trim_start();
-            let (name, _, _, _, _) = parse_sif_row::<String, String, f64, String, f64>(row)?;
-            let row_type = RowType::from_str(&type_str)?;
-            rows.push((name, row_type));
+            let (name, row_type, pairs) = self.parse_group_card(type_str, row)?;
+
+            self.rows.push((name.clone(), row_type));
+            self.entries.extend(
+                pairs
+                    .into_iter()
+                    .map(|(col_name, value)| (name.clone(), col_name, value)),
+            );
         }
 
-        self.rows = rows;
         Ok(&self.rows)
     }
 
-    fn parse_columns(&self, _input: &str) -> Result<(), ParseError> {
-        Err(ParseError {
-            message: "Column definitions are not supported in this version".to_string(),
+    /// Parses a card of the `ROWS`/`GROUPS` section into the row name, its
+    /// single-letter type and the `(col_name, value)` pairs it carries.
+    ///
+    /// `'SCALE'` pairs and `D`-type combinations are recorded on the parser
+    /// instead of being returned. A blank type refers to a row defined by an
+    /// earlier card.
+    fn parse_group_card(&mut self, type_str: &str, row: &str) -> Result<GroupCard, ParseError> {
+        let (f1, f2, f3, f4, f5) = parse_sif_row::<String, String, String, String, String>(row)?;
+
+        let row_type = if type_str.is_empty() {
+            self.rows
+                .iter()
+                .find(|(name, _)| *name == f1)
+                .map(|(_, row_type)| *row_type)
+                .ok_or_else(|| ParseError {
+                    message: format!("Missing row type for row: {}", f1),
+                })?
+        } else {
+            RowType::from_str(type_str)?
+        };
+
+        let (name, pairs) = match row_type {
+            RowType::N | RowType::G | RowType::L | RowType::E => {
+                (f1, vec![(f2, parse_value(&f3)?), (f4, parse_value(&f5)?)])
+            }
+            RowType::XN | RowType::XG | RowType::XL | RowType::XE => (
+                self.expand_name(&f1)?,
+                vec![
+                    (self.expand_name(&f2)?, parse_value(&f3)?),
+                    (self.expand_name(&f4)?, parse_value(&f5)?),
+                ],
+            ),
+            RowType::ZN | RowType::ZG | RowType::ZL | RowType::ZE => {
+                let name = self.expand_name(&f1)?;
+                if f2.is_empty() {
+                    (name, Vec::new())
+                } else {
+                    (name, vec![(self.expand_name(&f2)?, self.parameter(&f3)?)])
+                }
+            }
+            RowType::DN | RowType::DG | RowType::DL | RowType::DE => {
+                let name = self.expand_name(&f1)?;
+                self.combinations.push((
+                    name.clone(),
+                    self.expand_name(&f2)?,
+                    parse_value(&f3)?,
+                    self.expand_name(&f4)?,
+                    parse_value(&f5)?,
+                ));
+                (name, Vec::new())
+            }
+        };
+
+        let mut entries = Vec::new();
+        for (col_name, value) in pairs {
+            if col_name == "'SCALE'" {
+                self.scales.push((name.clone(), value));
+            } else if !col_name.is_empty() {
+                entries.push((col_name, value));
+            }
+        }
+
+        Ok((name, row_type.base(), entries))
+    }
+
+    /// Returns the value of a parameter. Numeric literals evaluate to
+    /// themselves.
+    fn parameter(&self, name: &str) -> Result<f64, ParseError> {
+        if let Some(value) = self.parameters.get(name) {
+            return Ok(*value);
+        }
+
+        if name.is_empty() {
+            return Err(ParseError {
+                message: "Missing parameter name".to_string(),
+            });
+        }
+
+        parse_value(name).map_err(|_| ParseError {
+            message: format!("Undefined parameter: {}", name),
         })
     }
 
+    /// Evaluates a parameter definition card and records its value.
+    ///
+    /// The first letter of the code gives the kind of parameter (`I`nteger,
+    /// `R`eal or `A`rray element), the second the operation:
+    ///
+    /// | Code | Value of the parameter in field 2 |
+    /// |------|-----------------------------------|
+    /// | `IE` | the number in field 4 |
+    /// | `IR` | the parameter in field 3, converted |
+    /// | `IA` | field 3 + the number in field 4 |
+    /// | `IS` | the number in field 4 − field 3 |
+    /// | `IM` | field 3 × the number in field 4 |
+    /// | `ID` | the number in field 4 / field 3 |
+    /// | `I=` | field 3 |
+    /// | `I+`, `I-`, `I*`, `I/` | field 3 combined with the parameter in field 5 |
+    /// | `RF` | the intrinsic function in field 3 applied to the number in field 4 |
+    /// | `R(` | the intrinsic function in field 3 applied to the parameter in field 5 |
+    ///
+    /// Integer results are truncated towards zero.
+    fn define_parameter(&mut self, code: &str, fields: &[&str]) -> Result<(), ParseError> {
+        let field = |index: usize| fields.get(index).copied().unwrap_or("");
+        let param = |index: usize| -> Result<f64, ParseError> {
+            self.parameter(&self.expand_name(field(index))?)
+        };
+
+        let name = self.expand_name(field(0))?;
+        let value = match &code[1..] {
+            "E" => parse_value(field(1))?,
+            "I" | "R" | "=" => param(1)?,
+            "A" => param(1)? + parse_value(field(2))?,
+            "S" => parse_value(field(2))? - param(1)?,
+            "M" => param(1)? * parse_value(field(2))?,
+            "D" => parse_value(field(2))? / param(1)?,
+            "+" => param(1)? + param(2)?,
+            "-" => param(1)? - param(2)?,
+            "*" => param(1)? * param(2)?,
+            "/" => param(1)? / param(2)?,
+            "F" => intrinsic(field(1), parse_value(field(2))?)?,
+            "(" => intrinsic(field(1), param(2)?)?,
+            _ => {
+                return Err(ParseError {
+                    message: format!("Unknown parameter definition: {}", code),
+                });
+            }
+        };
+
+        let value = if code.starts_with('I') {
+            value.trunc()
+        } else {
+            value
+        };
+
+        self.parameters.insert(name, value);
+        Ok(())
+    }
+
+    /// Prepares the body of a section for parsing: `DO` loops are expanded,
+    /// parameter definitions are evaluated and removed, and the remaining
+    /// cards are normalised with [`SifParser::normalize_card`].
+    fn preprocess(&mut self, indicator: Indicator, input: &str) -> Result<String, ParseError> {
+        let lines: Vec<&str> = input.lines().collect();
+        let mut output = String::new();
+
+        self.expand_lines(indicator, &lines, &mut output)?;
+
+        Ok(output)
+    }
+
+    /// Expands a block of cards into `output`, running every `DO` loop it
+    /// contains.
+    ///
+    /// A loop `DO I start end` runs until its matching `OD I`, or until `ND`,
+    /// which closes all open loops. An optional `DI I step` card inside the
+    /// loop sets its increment (default 1). The loop variable is an integer
+    /// parameter, so cards in the body can use it in indexed names.
+    fn expand_lines(
+        &mut self,
+        indicator: Indicator,
+        lines: &[&str],
+        output: &mut String,
+    ) -> Result<(), ParseError> {
+        let mut index = 0;
+
+        while index < lines.len() {
+            let line = lines[index];
+            let code = card_code(line).unwrap_or("");
+            let fields: Vec<&str> = line.get(3..).unwrap_or("").split_whitespace().collect();
+            index += 1;
+
+            match code {
+                "DO" => {
+                    let variable = fields.first().copied().unwrap_or("");
+                    let start = self.parameter(fields.get(1).copied().unwrap_or(""))? as i64;
+                    let end = self.parameter(fields.get(2).copied().unwrap_or(""))? as i64;
+
+                    let (body_end, next) = find_loop_end(lines, index);
+                    let body = &lines[index..body_end];
+                    index = next;
+
+                    let step = match find_loop_step(body, variable) {
+                        Some(step) => self.parameter(step)? as i64,
+                        None => 1,
+                    };
+                    if step == 0 {
+                        return Err(ParseError {
+                            message: format!("Zero increment for loop variable: {}", variable),
+                        });
+                    }
+
+                    let mut value = start;
+                    while (step > 0 && value <= end) || (step < 0 && value >= end) {
+                        self.parameters.insert(variable.to_string(), value as f64);
+                        self.expand_lines(indicator, body, output)?;
+                        value += step;
+                    }
+                }
+                // Increments are read when the loop starts; stray loop ends
+                // are ignored.
+                "DI" | "OD" | "ND" => {}
+                _ if is_parameter_code(code) => self.define_parameter(code, &fields)?,
+                _ => {
+                    *output += &self.normalize_card(indicator, line)?;
+                    *output += "\n";
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Rewrites an `X` or `Z` card of a section into the equivalent plain
+    /// card: indexed names are expanded, and for `Z` cards the parameter named
+    /// in the last field is replaced by its value. Other cards are returned
+    /// unchanged.
+    ///
+    /// Field positions are kept, so blank fields stay blank. Row cards keep
+    /// their code, which [`SifParser::parse_group_card`] still needs, but are
+    /// expanded here so that cards inside loops see the current loop index.
+    /// In `COLUMNS` an `X`/`Z` card is only treated this way when it names an
+    /// indexed column or refers to a parameter, otherwise it marks an
+    /// integer/binary column. A `ZV` card in `ELEMENT USES` binds a column,
+    /// so its last field is a name rather than a value.
+    fn normalize_card(&self, indicator: Indicator, line: &str) -> Result<String, ParseError> {
+        let code = card_code(line).unwrap_or("");
+        let last_token = line.split_whitespace().last().unwrap_or("");
+
+        let base = match (indicator, code) {
+            (Indicator::Columns | Indicator::Variables, "X") if line[3..].contains('(') => "",
+            (Indicator::Columns | Indicator::Variables, "Z")
+                if parse_value(last_token).is_err() =>
+            {
+                ""
+            }
+            (
+                Indicator::Constants
+                | Indicator::Rhs
+                | Indicator::RhsPrime
+                | Indicator::Ranges
+                | Indicator::Quadratic
+                | Indicator::Hessian
+                | Indicator::Quads
+                | Indicator::QuadObjective
+                | Indicator::QSection
+                | Indicator::QMatrix,
+                "X" | "Z",
+            ) => "",
+            (Indicator::Bounds | Indicator::ObjectBounds, "XL" | "ZL") => "LO",
+            (Indicator::Bounds | Indicator::ObjectBounds, "XU" | "ZU") => "UP",
+            (Indicator::Bounds, "XX" | "ZX") => "FX",
+            (Indicator::Bounds, "XR") => "FR",
+            (Indicator::Bounds, "XM") => "MI",
+            (Indicator::Bounds, "XP") => "PL",
+            (Indicator::StartPoint, "X" | "Z") => "",
+            (Indicator::StartPoint, "XV" | "ZV") => "V",
+            (Indicator::StartPoint, "XM" | "ZM") => "M",
+            (Indicator::ElementUses, "XT") => "T",
+            (Indicator::ElementUses, "XV" | "ZV") => "V",
+            (Indicator::ElementUses, "XP" | "ZP") => "P",
+            (Indicator::GroupUses, "XT") => "T",
+            (Indicator::GroupUses, "XE" | "ZE") => "E",
+            (Indicator::GroupUses, "XP" | "ZP") => "P",
+            (Indicator::Groups | Indicator::Rows | Indicator::Constraints, _)
+                if code.len() == 2 && code.starts_with(['X', 'Z', 'D']) =>
+            {
+                code
+            }
+            _ => return Ok(line.to_string()),
+        };
+
+        let takes_value =
+            code.starts_with('Z') && !(indicator == Indicator::ElementUses && code == "ZV");
+        let last = line.split_whitespace().count() - 1;
+        let rest = self.replace_tokens(&line[3..], |index, token| {
+            if takes_value && index > 0 && index + 1 == last {
+                Ok(self.parameter(&self.expand_name(token)?)?.to_string())
+            } else {
+                self.expand_name(token)
+            }
+        })?;
+
+        Ok(format!(" {:<2}{}", base, rest))
+    }
+
+    /// Maps every whitespace-separated token of `line` through `f`, which
+    /// also receives the index of the token, keeping the whitespace between
+    /// tokens intact.
+    fn replace_tokens(
+        &self,
+        line: &str,
+        f: impl Fn(usize, &str) -> Result<String, ParseError>,
+    ) -> Result<String, ParseError> {
+        static RE_TOKEN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\S+").unwrap());
+
+        let mut output = String::new();
+        let mut end = 0;
+        for (index, token) in RE_TOKEN.find_iter(line).enumerate() {
+            output += &line[end..token.start()];
+            output += &f(index, token.as_str())?;
+            end = token.end();
+        }
+        output += &line[end..];
+
+        Ok(output)
+    }
+
+    /// Expands the indices of an array name using the current parameter
+    /// values, e.g. `X(I,2)` with `I = 1` becomes `X1,2`. Names without
+    /// indices are returned unchanged.
+    fn expand_name(&self, name: &str) -> Result<String, ParseError> {
+        let (Some(open), Some(close)) = (name.find('('), name.rfind(')')) else {
+            return Ok(name.to_string());
+        };
+
+        let indices = name[open + 1..close]
+            .split(',')
+            .map(|index| Ok((self.parameter(index.trim())? as i64).to_string()))
+            .collect::<Result<Vec<String>, ParseError>>()?;
+
+        Ok(format!(
+            "{}{}{}",
+            &name[..open],
+            indices.join(","),
+            &name[close + 1..]
+        ))
+    }
+
+    /// Adds the entries of `D`-type rows, each a linear combination of the
+    /// entries of two other rows.
+    fn resolve_combinations(&mut self) -> Result<(), ParseError> {
+        for (name, row_1, mult_1, row_2, mult_2) in self.combinations.clone() {
+            let mut combined: Vec<(String, f64)> = Vec::new();
+
+            for (row_name, mult) in [(row_1, mult_1), (row_2, mult_2)] {
+                if row_name.is_empty() {
+                    continue;
+                }
+                if !self.rows.iter().any(|(name, _)| *name == row_name) {
+                    return Err(ParseError {
+                        message: format!("Row combination references undefined row: {}", row_name),
+                    });
+                }
+
+                for (_, col_name, value) in self.entries.iter().filter(|(row, ..)| *row == row_name)
+                {
+                    match combined.iter_mut().find(|(name, _)| name == col_name) {
+                        Some((_, combined_value)) => *combined_value += mult * value,
+                        None => combined.push((col_name.clone(), mult * value)),
+                    }
+                }
+            }
+
+            self.entries.extend(
+                combined
+                    .into_iter()
+                    .map(|(col_name, value)| (name.clone(), col_name, value)),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn parse_columns(&mut self, input: &str) -> Result<&Vec<(String, ColumnType)>, ParseError> {
+        let trimmed = input.lines().clone().next().ok_or_else(|| ParseError {
+            message: "COLUMNS section is empty".to_string(),
+        })?;
+
+        let re = Regex::new(r"^(\s*[XZ]?\s+)\S")
+            .unwrap()
+            .captures(trimmed)
+            .ok_or_else(|| ParseError {
+                message: "Failed to get separator from COLUMNS section".to_string(),
+            })?;
+
+        self.sep = Some(
+            re.get(1)
+                .ok_or_else(|| ParseError {
+                    message: "Failed to extract column type from COLUMNS section".to_string(),
+                })?
+                .as_str()
+                .len() as i8,
+        );
+
+        let mut cols = Vec::new();
+        let mut col_added = HashSet::new();
+        let mut integer = false;
+
+        for row in input.lines() {
+            if let Some(marker) = parse_marker(row)? {
+                integer = marker;
+                continue;
+            }
+
+            let sep = self.sep.ok_or_else(|| ParseError {
+                message: "Separator not set before parsing entries".to_string(),
+            })?;
+            let type_str = row[..sep as usize].trim();
+            let row = row[sep as usize..].trim_start();
+            let (name, _, _, _, _) = parse_sif_row::<String, String, f64, String, f64>(row)?;
+            let col_type = match (type_str, integer) {
+                ("", true) => ColumnType::X,
+                _ => ColumnType::from_str(type_str)?,
+            };
+
+            if col_added.insert(name.clone()) {
+                cols.push((name, col_type));
+            }
+        }
+
+        self.cols = cols;
+        Ok(&self.cols)
+    }
+
     fn parse_entries(
         &mut self,
         input: &str,
@@ -237,21 +928,34 @@ impl SifParser {
     ) -> Result<&Vec<(String, String, f64)>, ParseError> {
         let mut entries = Vec::new();
 
-        // let row_added = HashSet::new();
+        let mut row_added = HashSet::new();
         let mut col_added = HashSet::new();
+        let mut integer = false;
 
         for row in input.lines() {
+            if let Some(marker) = parse_marker(row)? {
+                integer = marker;
+                continue;
+            }
+
             let sep = self.sep.ok_or_else(|| ParseError {
                 message: "Separator not set before parsing entries".to_string(),
             })?;
+            let type_str = row[..sep as usize].trim();
             let row = row[sep as usize..].trim_start();
-            let (f1, f2, val1, f4, val2) = parse_sif_row::<String, String, f64, String, f64>(row)?;
 
             match major {
                 Major::Row => {
+                    let (f1, f2, val1, f4, val2) =
+                        parse_sif_row::<String, String, f64, String, f64>(row)?;
+
                     // Add columns if necessary
                     if !col_added.contains(&f1) {
-                        self.cols.push((f1.clone(), ColumnType::__));
+                        let col_type = match (type_str, integer) {
+                            ("", true) => ColumnType::X,
+                            _ => ColumnType::from_str(type_str)?,
+                        };
+                        self.cols.push((f1.clone(), col_type));
                         col_added.insert(f1.clone());
                     }
 
@@ -262,16 +966,25 @@ impl SifParser {
                     }
                 }
                 Major::Column => {
-                    entries.push((f2, f1.clone(), val1));
+                    let (name, row_type, pairs) = self.parse_group_card(type_str, row)?;
 
-                    if val2 != 0.0 {
-                        entries.push((f4, f1.clone(), val2));
+                    // Add rows if necessary; the row type is only required on
+                    // the first line of each row.
+                    if !row_added.contains(&name) {
+                        self.rows.push((name.clone(), row_type));
+                        row_added.insert(name.clone());
                     }
+
+                    entries.extend(
+                        pairs
+                            .into_iter()
+                            .map(|(col_name, value)| (name.clone(), col_name, value)),
+                    );
                 }
             }
         }
 
-        self.entries = entries;
+        self.entries.extend(entries);
         Ok(&self.entries)
     }
 
@@ -285,6 +998,7 @@ impl SifParser {
             let row = row[sep as usize..].to_string();
             let (f1, f2, val1, f4, val2) =
                 parse_sif_row::<String, String, f64, String, f64>(row.as_str())?;
+            let f1 = set_name(&row, f1);
 
             rhs.push((f1.clone(), f2, val1));
 
@@ -297,10 +1011,27 @@ impl SifParser {
         Ok(&self.rhs)
     }
 
-    fn parse_ranges(&self, _input: &str) -> Result<Vec<(String, f64)>, ParseError> {
-        Err(ParseError {
-            message: "Range entries are not supported in this version".to_string(),
-        })
+    fn parse_ranges(&mut self, input: &str) -> Result<&Vec<(String, String, f64)>, ParseError> {
+        let mut ranges = Vec::new();
+
+        for row in input.lines() {
+            let sep = self.sep.ok_or_else(|| ParseError {
+                message: "Separator not set before parsing entries".to_string(),
+            })?;
+            let row = row[sep as usize..].to_string();
+            let (f1, f2, val1, f4, val2) =
+                parse_sif_row::<String, String, f64, String, f64>(row.as_str())?;
+            let f1 = set_name(&row, f1);
+
+            ranges.push((f1.clone(), f2, val1));
+
+            if !f4.is_empty() {
+                ranges.push((f1.clone(), f4, val2));
+            }
+        }
+
+        self.ranges = ranges;
+        Ok(&self.ranges)
     }
 
     fn parse_bounds(
@@ -316,6 +1047,7 @@ impl SifParser {
             let type_str = row[..sep as usize].trim();
             let row = row[sep as usize..].to_string();
             let (f1, f2, val1, _, _) = parse_sif_row::<String, String, f64, String, f64>(&row)?;
+            let f1 = set_name(&row, f1);
             bounds.push((f1.clone(), BoundType::from_str(type_str)?, f2, val1));
         }
 
@@ -323,57 +1055,460 @@ impl SifParser {
         Ok(&self.bounds)
     }
 
-    fn parse_quadratic(&mut self, input: &str) -> Result<&Vec<(String, String, f64)>, ParseError> {
-        let mut qterms = Vec::new();
+    fn parse_quadratic(
+        &mut self,
+        input: &str,
+        indicator: Indicator,
+    ) -> Result<&Vec<(String, String, f64)>, ParseError> {
+        let mut qterms = Vec::new();
 
         for row in input.lines() {
             let sep = self.sep.ok_or_else(|| ParseError {
                 message: "Separator not set before parsing entries".to_string(),
             })?;
             let row = row[sep as usize..].trim_start();
-            let (f1, f2, val1, _f4, _val2) =
-                parse_sif_row::<String, String, f64, String, f64>(row)?;
-            qterms.push((f1.clone(), f2.clone(), val1));
+            let (f1, f2, val1, f4, val2) = parse_sif_row::<String, String, f64, String, f64>(row)?;
+            qterms.push((f1.clone(), f2, val1));
+
+            if !f4.is_empty() {
+                qterms.push((f1, f4, val2));
+            }
+        }
+
+        // Every other section gives each off-diagonal pair once, in either
+        // triangle; QMATRIX gives both, so each entry counts for half.
+        for (col_name_i, col_name_j, coeff) in &qterms {
+            let coeff = if indicator == Indicator::QMatrix && col_name_i != col_name_j {
+                0.5 * coeff
+            } else {
+                *coeff
+            };
+            self.quadratic_terms
+                .push((col_name_i.clone(), col_name_j.clone(), coeff));
         }
 
-        self.quadratic = qterms;
+        self.quadratic.extend(qterms);
         Ok(&self.quadratic)
     }
 
-    fn parse_start_point(&self, _input: &str) -> Result<Vec<(String, f64)>, ParseError> {
-        Err(ParseError {
-            message: "Start point entries are not supported in this version".to_string(),
-        })
+    fn parse_start_point(
+        &mut self,
+        input: &str,
+    ) -> Result<&Vec<(String, String, f64)>, ParseError> {
+        let mut start_point = Vec::new();
+
+        for row in input.lines() {
+            let sep = self.sep.ok_or_else(|| ParseError {
+                message: "Separator not set before parsing entries".to_string(),
+            })?;
+            let type_str = row[..sep as usize].trim();
+            let row = row[sep as usize..].to_string();
+            let (f1, f2, val1, f4, val2) =
+                parse_sif_row::<String, String, f64, String, f64>(row.as_str())?;
+            let f1 = set_name(&row, f1);
+
+            match type_str {
+                "" | "V" => {}
+                // Lagrange multiplier estimates are not stored.
+                "M" => continue,
+                _ => {
+                    return Err(ParseError {
+                        message: format!("Unknown start point type: {}", type_str),
+                    });
+                }
+            }
+
+            for (name, value) in [(f2, val1), (f4, val2)] {
+                // Blank-type entries may also name rows, giving multiplier
+                // estimates; only variable values are kept.
+                if name.is_empty()
+                    || (type_str.is_empty() && self.rows.iter().any(|(row, _)| *row == name))
+                {
+                    continue;
+                }
+                start_point.push((f1.clone(), name, value));
+            }
+        }
+
+        self.start_point.extend(start_point);
+        Ok(&self.start_point)
     }
 
-    fn parse_element_type(&self, _input: &str) -> Result<(), ParseError> {
-        Err(ParseError {
-            message: "Element type entries are not supported in this version".to_string(),
-        })
+    fn parse_element_type(&mut self, input: &str) -> Result<&Vec<ElementType>, ParseError> {
+        for row in input.lines() {
+            let sep = self.sep.ok_or_else(|| ParseError {
+                message: "Separator not set before parsing entries".to_string(),
+            })?;
+            let type_str = row[..sep as usize].trim();
+            let row = row[sep as usize..].to_string();
+            let (f1, f2, f3, _, _) = parse_sif_row::<String, String, String, String, String>(&row)?;
+
+            let element_type = match self.element_types.iter_mut().find(|t| t.name == f1) {
+                Some(element_type) => element_type,
+                None => {
+                    self.element_types.push(ElementType {
+                        name: f1.clone(),
+                        ..Default::default()
+                    });
+                    self.element_types.last_mut().unwrap()
+                }
+            };
+
+            let names = match type_str {
+                "EV" => &mut element_type.elemental_variables,
+                "IV" => &mut element_type.internal_variables,
+                "EP" => &mut element_type.parameters,
+                _ => {
+                    return Err(ParseError {
+                        message: format!("Unknown element type entry: {}", type_str),
+                    });
+                }
+            };
+
+            for name in [f2, f3] {
+                if name.is_empty() {
+                    continue;
+                }
+                if names.contains(&name) {
+                    return Err(ParseError {
+                        message: format!("Duplicate name {} in element type: {}", name, f1),
+                    });
+                }
+                names.push(name);
+            }
+        }
+
+        Ok(&self.element_types)
     }
 
-    fn parse_element_uses(&self, _input: &str) -> Result<(), ParseError> {
-        Err(ParseError {
-            message: "Element uses entries are not supported in this version".to_string(),
-        })
+    fn parse_element_uses(&mut self, input: &str) -> Result<&Vec<ElementUse>, ParseError> {
+        for row in input.lines() {
+            let sep = self.sep.ok_or_else(|| ParseError {
+                message: "Separator not set before parsing entries".to_string(),
+            })?;
+            let type_str = row[..sep as usize].trim();
+            let row = row[sep as usize..].to_string();
+
+            match type_str {
+                "T" => {
+                    let (f1, f2, _, _, _) =
+                        parse_sif_row::<String, String, String, String, String>(&row)?;
+                    if f1 == "'DEFAULT'" {
+                        self.default_element_type = Some(f2);
+                    } else {
+                        self.element_use(&f1).element_type = f2;
+                    }
+                }
+                "V" => {
+                    let (f1, f2, f3, _, _) =
+                        parse_sif_row::<String, String, String, String, String>(&row)?;
+                    self.element_use(&f1).variables.insert(f2, f3);
+                }
+                "P" => {
+                    let (f1, f2, f3, f4, f5) =
+                        parse_sif_row::<String, String, f64, String, f64>(&row)?;
+                    let element = self.element_use(&f1);
+                    element.parameters.insert(f2, f3);
+                    if !f4.is_empty() {
+                        element.parameters.insert(f4, f5);
+                    }
+                }
+                _ => {
+                    return Err(ParseError {
+                        message: format!("Unknown element uses entry: {}", type_str),
+                    });
+                }
+            }
+        }
+
+        Ok(&self.element_uses)
     }
 
-    fn parse_group_type(&self, _input: &str) -> Result<(), ParseError> {
-        Err(ParseError {
-            message: "Group type entries are not supported in this version".to_string(),
-        })
+    /// Returns the element named `name`, declaring it on first use.
+    fn element_use(&mut self, name: &str) -> &mut ElementUse {
+        match self
+            .element_uses
+            .iter()
+            .position(|element| element.name == name)
+        {
+            Some(index) => &mut self.element_uses[index],
+            None => {
+                self.element_uses.push(ElementUse {
+                    name: name.to_string(),
+                    ..Default::default()
+                });
+                self.element_uses.last_mut().unwrap()
+            }
+        }
     }
 
-    fn parse_group_uses(&self, _input: &str) -> Result<(), ParseError> {
-        Err(ParseError {
-            message: "Group uses entries are not supported in this version".to_string(),
-        })
+    /// Gives the `'DEFAULT'` element type to every element declared without
+    /// a `T` card, and the `'DEFAULT'` group type to every group (row)
+    /// without one.
+    fn resolve_default_types(&mut self) {
+        if let Some(default) = &self.default_element_type {
+            for element in &mut self.element_uses {
+                if element.element_type.is_empty() {
+                    element.element_type = default.clone();
+                }
+            }
+        }
+
+        if let Some(default) = self.default_group_type.clone() {
+            for (row_name, _) in self.rows.clone() {
+                let group = self.group_use(&row_name);
+                if group.group_type.is_empty() {
+                    group.group_type = default.clone();
+                }
+            }
+        }
     }
 
-    fn parse_object_bounds(&self, _input: &str) -> Result<(), ParseError> {
-        Err(ParseError {
-            message: "Object bounds entries are not supported in this version".to_string(),
-        })
+    fn parse_group_type(&mut self, input: &str) -> Result<&Vec<GroupType>, ParseError> {
+        for row in input.lines() {
+            let sep = self.sep.ok_or_else(|| ParseError {
+                message: "Separator not set before parsing entries".to_string(),
+            })?;
+            let type_str = row[..sep as usize].trim();
+            let row = row[sep as usize..].to_string();
+            let (f1, f2, f3, _, _) = parse_sif_row::<String, String, String, String, String>(&row)?;
+
+            let group_type = match self.group_types.iter_mut().find(|t| t.name == f1) {
+                Some(group_type) => group_type,
+                None => {
+                    self.group_types.push(GroupType {
+                        name: f1.clone(),
+                        ..Default::default()
+                    });
+                    self.group_types.last_mut().unwrap()
+                }
+            };
+
+            match type_str {
+                "GV" => {
+                    if !group_type.group_variable.is_empty() {
+                        return Err(ParseError {
+                            message: format!("Duplicate group variable in group type: {}", f1),
+                        });
+                    }
+                    group_type.group_variable = f2;
+                }
+                "GP" => {
+                    for name in [f2, f3] {
+                        if name.is_empty() {
+                            continue;
+                        }
+                        if group_type.parameters.contains(&name) {
+                            return Err(ParseError {
+                                message: format!("Duplicate name {} in group type: {}", name, f1),
+                            });
+                        }
+                        group_type.parameters.push(name);
+                    }
+                }
+                _ => {
+                    return Err(ParseError {
+                        message: format!("Unknown group type entry: {}", type_str),
+                    });
+                }
+            }
+        }
+
+        Ok(&self.group_types)
+    }
+
+    fn parse_group_uses(&mut self, input: &str) -> Result<&Vec<GroupUse>, ParseError> {
+        for row in input.lines() {
+            let sep = self.sep.ok_or_else(|| ParseError {
+                message: "Separator not set before parsing entries".to_string(),
+            })?;
+            let type_str = row[..sep as usize].trim();
+            let row = row[sep as usize..].to_string();
+
+            match type_str {
+                "T" => {
+                    let (f1, f2, _, _, _) =
+                        parse_sif_row::<String, String, String, String, String>(&row)?;
+                    if f1 == "'DEFAULT'" {
+                        self.default_group_type = Some(f2);
+                    } else {
+                        self.group_use(&f1).group_type = f2;
+                    }
+                }
+                "E" => {
+                    // After the group name come pairs of an element and its
+                    // weight; a missing last weight defaults to 1.
+                    let fields: Vec<&str> = row.split_whitespace().collect();
+                    let name = fields.first().copied().unwrap_or("");
+                    let mut elements: Vec<(String, f64)> = Vec::new();
+                    for pair in fields.get(1..).unwrap_or_default().chunks(2) {
+                        let weight = match pair.get(1) {
+                            Some(weight) => parse_value(weight)?,
+                            None => 1.0,
+                        };
+                        elements.push((pair[0].to_string(), weight));
+                    }
+                    self.group_use(name).elements.extend(elements);
+                }
+                "P" => {
+                    let (f1, f2, f3, f4, f5) =
+                        parse_sif_row::<String, String, f64, String, f64>(&row)?;
+                    let group = self.group_use(&f1);
+                    group.parameters.insert(f2, f3);
+                    if !f4.is_empty() {
+                        group.parameters.insert(f4, f5);
+                    }
+                }
+                _ => {
+                    return Err(ParseError {
+                        message: format!("Unknown group uses entry: {}", type_str),
+                    });
+                }
+            }
+        }
+
+        Ok(&self.group_uses)
+    }
+
+    /// Returns the group named `name`, declaring it on first use.
+    fn group_use(&mut self, name: &str) -> &mut GroupUse {
+        match self.group_uses.iter().position(|group| group.name == name) {
+            Some(index) => &mut self.group_uses[index],
+            None => {
+                self.group_uses.push(GroupUse {
+                    name: name.to_string(),
+                    ..Default::default()
+                });
+                self.group_uses.last_mut().unwrap()
+            }
+        }
+    }
+
+    /// Parses the element functions of an `ELEMENTS` section; the element
+    /// types must already be known.
+    fn parse_elements(&mut self, input: &str) -> Result<&Vec<ElementFunction>, ParseError> {
+        let functions = functions::parse_element_functions(input, &self.element_types)?;
+        self.element_functions.extend(functions);
+        Ok(&self.element_functions)
+    }
+
+    /// Parses the group functions of a `GROUPS` section that follows the
+    /// first `ENDATA`; the group types must already be known.
+    fn parse_group_functions(&mut self, input: &str) -> Result<&Vec<GroupFunction>, ParseError> {
+        let functions = functions::parse_group_functions(input, &self.group_types)?;
+        self.group_functions.extend(functions);
+        Ok(&self.group_functions)
+    }
+
+    fn parse_object_bounds(
+        &mut self,
+        input: &str,
+    ) -> Result<&Vec<(String, BoundType, f64)>, ParseError> {
+        let mut object_bounds = Vec::new();
+
+        for row in input.lines() {
+            let sep = self.sep.ok_or_else(|| ParseError {
+                message: "Separator not set before parsing entries".to_string(),
+            })?;
+            let type_str = row[..sep as usize].trim();
+            let row = row[sep as usize..].to_string();
+            let (f1, val, _, _, _) = parse_sif_row::<String, f64, String, String, f64>(&row)?;
+            let f1 = set_name(&row, f1);
+
+            let bound_type = match type_str {
+                "LO" => BoundType::Lo,
+                "UP" => BoundType::Up,
+                _ => {
+                    return Err(ParseError {
+                        message: format!("Unknown object bound type: {}", type_str),
+                    });
+                }
+            };
+
+            object_bounds.push((f1, bound_type, val));
+        }
+
+        self.object_bounds.extend(object_bounds);
+        Ok(&self.object_bounds)
+    }
+
+    /// Reads a free-MPS `OBJSENSE` or `OBJNAME` card, whose value is either
+    /// on the indicator line (`field`) or on the indented line below it.
+    fn parse_objective_card(
+        &mut self,
+        indicator: Indicator,
+        field: Option<&str>,
+        input: &str,
+    ) -> Result<(), ParseError> {
+        let value = field
+            .or_else(|| input.split_whitespace().next())
+            .ok_or_else(|| ParseError {
+                message: format!("Missing value for {}", indicator),
+            })?;
+
+        match indicator {
+            Indicator::ObjSense => self.objective_sense = ObjSense::from_str(value)?,
+            _ => self.objective_name = Some(value.to_string()),
+        }
+        Ok(())
+    }
+
+    /// Picks the objective row: the one named by the options or by `OBJNAME`,
+    /// otherwise the first free row in file order. The other free rows are
+    /// removed with all their data if the options ask for it.
+    fn select_objective(&mut self, options: &ParseOptions) -> Result<(), ParseError> {
+        // Without a ROWS section every row is free, in order of appearance.
+        let mut free_rows: Vec<String> = Vec::new();
+        if self.rows.is_empty() {
+            for (row_name, _, _) in &self.entries {
+                if !free_rows.contains(row_name) {
+                    free_rows.push(row_name.clone());
+                }
+            }
+        } else {
+            free_rows = self
+                .rows
+                .iter()
+                .filter(|(_, row_type)| row_type.base() == RowType::N)
+                .map(|(name, _)| name.clone())
+                .collect();
+        }
+
+        let objective_name = match options
+            .objective_row
+            .clone()
+            .or_else(|| self.objective_name.clone())
+        {
+            Some(name) if !free_rows.contains(&name) => {
+                return Err(ParseError {
+                    message: format!("Unknown objective row: {}", name),
+                });
+            }
+            Some(name) => Some(name),
+            None => free_rows.first().cloned(),
+        };
+
+        if options.drop_free_rows {
+            let dropped: HashSet<String> = free_rows
+                .into_iter()
+                .filter(|name| Some(name) != objective_name.as_ref())
+                .collect();
+
+            self.rows.retain(|(name, _)| !dropped.contains(name));
+            self.entries
+                .retain(|(row_name, _, _)| !dropped.contains(row_name));
+            self.rhs
+                .retain(|(_, row_name, _)| !dropped.contains(row_name));
+            self.ranges
+                .retain(|(_, row_name, _)| !dropped.contains(row_name));
+            self.scales
+                .retain(|(row_name, _)| !dropped.contains(row_name));
+            self.group_uses
+                .retain(|group| !dropped.contains(&group.name));
+        }
+
+        self.objective_name = objective_name;
+        Ok(())
     }
 
     fn validate(&self) -> Result<bool, ParseError> {
@@ -424,6 +1559,15 @@ impl SifParser {
             }
         }
 
+        // Validate range entries reference defined rows
+        for (_, row_name, _) in &self.ranges {
+            if !constraints.contains(row_name) {
+                return Err(ParseError {
+                    message: format!("Range entry references undefined row: {}", row_name),
+                });
+            }
+        }
+
         // Validate bounds reference defined columns
         for (_, _, col_name, _) in &self.bounds {
             if !vars.contains(col_name) {
@@ -433,6 +1577,15 @@ impl SifParser {
             }
         }
 
+        // Validate start point values reference defined columns
+        for (_, col_name, _) in &self.start_point {
+            if col_name != "'DEFAULT'" && !vars.contains(col_name) {
+                return Err(ParseError {
+                    message: format!("Start point references undefined column: {}", col_name),
+                });
+            }
+        }
+
         // Validate quadratic terms reference defined columns
         for (col_name_i, col_name_j, _) in &self.quadratic {
             if !vars.contains(col_name_i) {
@@ -447,10 +1600,143 @@ impl SifParser {
             }
         }
 
+        // Validate elements against their types and the defined columns
+        for element in &self.element_uses {
+            let element_type = self
+                .element_types
+                .iter()
+                .find(|element_type| element_type.name == element.element_type)
+                .ok_or_else(|| ParseError {
+                    message: if element.element_type.is_empty() {
+                        format!("Missing element type for element: {}", element.name)
+                    } else {
+                        format!(
+                            "Element {} references undefined element type: {}",
+                            element.name, element.element_type
+                        )
+                    },
+                })?;
+
+            for (variable, col_name) in &element.variables {
+                if !element_type.elemental_variables.contains(variable) {
+                    return Err(ParseError {
+                        message: format!(
+                            "Element {} references undefined elemental variable: {}",
+                            element.name, variable
+                        ),
+                    });
+                }
+                if !vars.contains(col_name) {
+                    return Err(ParseError {
+                        message: format!(
+                            "Element {} references undefined column: {}",
+                            element.name, col_name
+                        ),
+                    });
+                }
+            }
+            if let Some(variable) = element_type
+                .elemental_variables
+                .iter()
+                .find(|variable| !element.variables.contains_key(*variable))
+            {
+                return Err(ParseError {
+                    message: format!(
+                        "Element {} has unassigned elemental variable: {}",
+                        element.name, variable
+                    ),
+                });
+            }
+
+            for parameter in element.parameters.keys() {
+                if !element_type.parameters.contains(parameter) {
+                    return Err(ParseError {
+                        message: format!(
+                            "Element {} references undefined parameter: {}",
+                            element.name, parameter
+                        ),
+                    });
+                }
+            }
+            if let Some(parameter) = element_type
+                .parameters
+                .iter()
+                .find(|parameter| !element.parameters.contains_key(*parameter))
+            {
+                return Err(ParseError {
+                    message: format!(
+                        "Element {} has unassigned parameter: {}",
+                        element.name, parameter
+                    ),
+                });
+            }
+        }
+
+        // Validate groups against their rows, types and elements
+        for group in &self.group_uses {
+            if !constraints.contains(&group.name) {
+                return Err(ParseError {
+                    message: format!("Group uses entry references undefined row: {}", group.name),
+                });
+            }
+
+            for (element_name, _) in &group.elements {
+                if !self
+                    .element_uses
+                    .iter()
+                    .any(|element| element.name == *element_name)
+                {
+                    return Err(ParseError {
+                        message: format!(
+                            "Group {} references undefined element: {}",
+                            group.name, element_name
+                        ),
+                    });
+                }
+            }
+
+            let parameters = if group.group_type.is_empty() {
+                Vec::new()
+            } else {
+                self.group_types
+                    .iter()
+                    .find(|group_type| group_type.name == group.group_type)
+                    .map(|group_type| group_type.parameters.clone())
+                    .ok_or_else(|| ParseError {
+                        message: format!(
+                            "Group {} references undefined group type: {}",
+                            group.name, group.group_type
+                        ),
+                    })?
+            };
+
+            for parameter in group.parameters.keys() {
+                if !parameters.contains(parameter) {
+                    return Err(ParseError {
+                        message: format!(
+                            "Group {} references undefined parameter: {}",
+                            group.name, parameter
+                        ),
+                    });
+                }
+            }
+            if let Some(parameter) = parameters
+                .iter()
+                .find(|parameter| !group.parameters.contains_key(*parameter))
+            {
+                return Err(ParseError {
+                    message: format!(
+                        "Group {} has unassigned parameter: {}",
+                        group.name, parameter
+                    ),
+                });
+            }
+        }
+
         Ok(true)
     }
 
-    fn parse(input: &str) -> Result<SIF, ParseError> {
+    fn parse(input: &str, options: &ParseOptions) -> Result<SIF, ParseError> {
         let mut sif = SifParser {
             name: String::new(),
             major: None,
@@ -461,151 +1747,409 @@ impl SifParser {
             rhs: Vec::new(),
             ranges: Vec::new(),
             bounds: Vec::new(),
+            start_point: Vec::new(),
             quadratic: Vec::new(),
+            quadratic_terms: Vec::new(),
+            object_bounds: Vec::new(),
+            objective_sense: ObjSense::Min,
+            objective_name: None,
+            parameters: BTreeMap::new(),
+            scales: Vec::new(),
+            combinations: Vec::new(),
+            parameter_choices: BTreeMap::new(),
+            element_types: Vec::new(),
+            element_uses: Vec::new(),
+            default_element_type: None,
+            group_types: Vec::new(),
+            group_uses: Vec::new(),
+            default_group_type: None,
+            element_functions: Vec::new(),
+            group_functions: Vec::new(),
         };
 
         sif.name = sif.parse_name(input)?;
 
-        let cards = RE_CARDS.captures_iter(input);
+        let (input, parameter_choices) = select_parameters(input, options)?;
+        sif.parameter_choices = parameter_choices;
+
+        let input = strip_comments(&input);
+        let (data, functions) = match RE_ENDATA.find(&input) {
+            Some(endata) => input.split_at(endata.end()),
+            None => (input.as_str(), ""),
+        };
+        let cards = RE_CARDS.captures_iter(data);
         let mut major = None;
 
         for card in cards {
-            let indicator = Indicator::from_str(&card[1]).unwrap();
-            let content = &card[2];
+            let (indicator, field) = split_indicator(&card)?;
+            let content = &sif.preprocess(indicator, &card[3])?;
+            if content.is_empty() && !matches!(indicator, Indicator::ObjSense | Indicator::ObjName)
+            {
+                continue;
+            }
+
             match indicator {
                 Indicator::Groups | Indicator::Rows | Indicator::Constraints => {
-                    if major.is_none() {
+                    if let Some(major) = major {
+                        sif.parse_entries(content, major)?;
+                    } else {
                         major = Some(Major::Row);
                         sif.parse_rows(content)?;
-                    } else {
-                        sif.parse_entries(content, major.unwrap()).unwrap();
                     }
 
                     // sif.rows = parse_rows(content, Major::Row);
                 }
                 Indicator::Columns | Indicator::Variables => {
-                    if major.is_none() {
+                    if let Some(major) = major {
+                        sif.parse_entries(content, major)?;
+                    } else {
                         major = Some(Major::Column);
                         sif.parse_columns(content)?;
-                    } else {
-                        sif.parse_entries(content, major.unwrap()).unwrap();
                     }
                 }
                 Indicator::Constants | Indicator::Rhs | Indicator::RhsPrime => {
-                    sif.parse_rhs(content).unwrap();
+                    sif.parse_rhs(content)?;
                 }
                 Indicator::Ranges => {
-                    sif.parse_ranges(content).unwrap();
+                    sif.parse_ranges(content)?;
                 }
                 Indicator::Bounds => {
-                    sif.parse_bounds(content).unwrap();
+                    sif.parse_bounds(content)?;
                 }
                 Indicator::StartPoint => {
-                    sif.parse_start_point(content).unwrap();
+                    sif.parse_start_point(content)?;
                 }
                 Indicator::Quadratic
                 | Indicator::Hessian
                 | Indicator::Quads
                 | Indicator::QuadObjective
-                | Indicator::QSection => {
-                    sif.parse_quadratic(content).unwrap();
+                | Indicator::QSection
+                | Indicator::QMatrix => {
+                    sif.parse_quadratic(content, indicator)?;
                 }
                 Indicator::ElementType => {
-                    sif.parse_element_type(content).unwrap();
+                    sif.parse_element_type(content)?;
                 }
                 Indicator::ElementUses => {
-                    sif.parse_element_uses(content).unwrap();
+                    sif.parse_element_uses(content)?;
                 }
                 Indicator::GroupType => {
-                    sif.parse_group_type(content).unwrap();
+                    sif.parse_group_type(content)?;
                 }
                 Indicator::GroupUses => {
-                    sif.parse_group_uses(content).unwrap();
+                    sif.parse_group_uses(content)?;
                 }
                 Indicator::ObjectBounds => {
-                    sif.parse_object_bounds(content).unwrap();
+                    sif.parse_object_bounds(content)?;
+                }
+                Indicator::ObjSense | Indicator::ObjName => {
+                    sif.parse_objective_card(indicator, field, content)?;
                 }
                 _ => { /* Ignore other indicators for now */ }
             };
         }
 
+        sif.resolve_combinations()?;
+        sif.resolve_default_types();
+
         let _ = sif.validate()?;
+        sif.select_objective(options)?;
+
+        for card in RE_CARDS.captures_iter(functions) {
+            match split_indicator(&card)?.0 {
+                Indicator::Elements => {
+                    sif.parse_elements(&card[3])?;
+                }
+                Indicator::Groups => {
+                    sif.parse_group_functions(&card[3])?;
+                }
+                Indicator::Endata => {}
+                indicator => {
+                    return Err(ParseError {
+                        message: format!("Unexpected section after ENDATA: {}", indicator),
+                    });
+                }
+            }
+        }
 
         Ok(SIF::from(&sif))
     }
 }
 
-impl From<&SifParser> for SIF {
-    fn from(parser: &SifParser) -> Self {
-        let rows: BTreeMap<String, RowType> = parser
-            .rows
-            .iter()
-            .map(|(name, row_type)| (name.clone(), *row_type))
-            .collect();
+/// Accumulates raw bound records, in file order, into effective
+/// `(lower, upper)` bounds for every column that has at least one record.
+///
+/// Columns start from the default `[0, +inf)`. An `UP` or `UI` bound with a
+/// negative value on a column whose lower bound was never set explicitly also
+/// moves the lower bound to −∞, following the usual MPS convention. An `SC`
+/// bound only sets the upper bound; the zero alternative of a semi-continuous
+/// column is not part of its interval.
+fn resolve_bounds(records: &[(String, BoundType, String, f64)]) -> BTreeMap<String, (f64, f64)> {
+    let mut bounds: BTreeMap<String, (f64, f64)> = BTreeMap::new();
+    let mut lower_set = HashSet::new();
 
-        let cols: BTreeMap<String, ColumnType> = parser
-            .cols
-            .iter()
-            .map(|(name, col_type)| (name.clone(), *col_type))
-            .collect();
+    for (_, bound_type, col_name, value) in records {
+        let (lower, upper) = bounds
+            .entry(col_name.clone())
+            .or_insert((0.0, f64::INFINITY));
 
-        let entries: BTreeMap<(String, String), f64> = parser
-            .entries
+        match bound_type {
+            BoundType::Lo => {
+                *lower = *value;
+                lower_set.insert(col_name);
+            }
+            BoundType::Up => {
+                *upper = *value;
+                if *value < 0.0 && *lower == 0.0 && !lower_set.contains(col_name) {
+                    *lower = f64::NEG_INFINITY;
+                }
+            }
+            BoundType::Fx => {
+                *lower = *value;
+                *upper = *value;
+                lower_set.insert(col_name);
+            }
+            BoundType::Fr => {
+                *lower = f64::NEG_INFINITY;
+                *upper = f64::INFINITY;
+                lower_set.insert(col_name);
+            }
+            BoundType::Mi => {
+                *lower = f64::NEG_INFINITY;
+                lower_set.insert(col_name);
+            }
+            BoundType::Pl => {
+                *upper = f64::INFINITY;
+            }
+            BoundType::Bv => {
+                *lower = 0.0;
+                *upper = 1.0;
+                lower_set.insert(col_name);
+            }
+            BoundType::Li => {
+                *lower = *value;
+                lower_set.insert(col_name);
+            }
+            BoundType::Ui => {
+                *upper = *value;
+                if *value < 0.0 && *lower == 0.0 && !lower_set.contains(col_name) {
+                    *lower = f64::NEG_INFINITY;
+                }
+            }
+            BoundType::Sc => {
+                *upper = if *value == 0.0 { f64::INFINITY } else { *value };
+            }
+        }
+    }
+
+    bounds
+}
+
+impl From<&SifParser> for SIF {
+    fn from(parser: &SifParser) -> Self {
+        let rows: BTreeMap<String, RowType> = parser
+            .rows
             .iter()
-            .map(|(row_name, col_name, coeff)| ((row_name.clone(), col_name.clone()), *coeff))
+            .map(|(name, row_type)| (name.clone(), *row_type))
             .collect();
 
-        let rhs: BTreeMap<String, f64> = parser
-            .rhs
+        let cols: BTreeMap<String, ColumnType> = parser
+            .cols
             .iter()
-            .map(|(_rhs_name, row_name, value)| (row_name.clone(), *value))
+            .map(|(name, col_type)| (name.clone(), *col_type))
             .collect();
 
-        let bounds: BTreeMap<String, (BoundType, f64)> = parser
-            .bounds
+        let entries: BTreeMap<(String, String), f64> = parser
+            .entries
             .iter()
-            .map(|(_, bound_type, col_name, value)| ((col_name.clone()), (*bound_type, *value)))
+            .map(|(row_name, col_name, coeff)| ((row_name.clone(), col_name.clone()), *coeff))
             .collect();
 
-        let quadratic: BTreeMap<(String, String), f64> = parser
-            .quadratic
+        let rhs_sets = group_sets(&parser.rhs);
+
+        // Ranges on free rows carry no meaning and are dropped, as MPS readers do.
+        let ranges: Vec<(String, String, f64)> = parser
+            .ranges
             .iter()
-            .map(|(col_name_i, col_name_j, coeff)| {
-                ((col_name_i.clone(), col_name_j.clone()), *coeff)
+            .filter(|(_, row_name, _)| {
+                parser
+                    .rows
+                    .iter()
+                    .any(|(name, row_type)| name == row_name && *row_type != RowType::N)
             })
+            .cloned()
             .collect();
+        let range_sets = group_sets(&ranges);
+
+        let bound_records = parser.bounds.clone();
+
+        let start_points = group_sets(&parser.start_point);
 
-        let rows = if rows.len() > 0 {
+        // Terms repeated for the same pair add up.
+        let mut quadratic: BTreeMap<(String, String), f64> = BTreeMap::new();
+        for (col_name_i, col_name_j, coeff) in &parser.quadratic {
+            *quadratic
+                .entry((col_name_i.clone(), col_name_j.clone()))
+                .or_default() += coeff;
+        }
+
+        let row_scales: BTreeMap<String, f64> = parser.scales.iter().cloned().collect();
+        let parameters = parser.parameters.clone();
+
+        let mut object_bounds: Vec<(String, (f64, f64))> = Vec::new();
+        for (set_name, bound_type, value) in &parser.object_bounds {
+            let index = match object_bounds.iter().position(|(name, _)| name == set_name) {
+                Some(index) => index,
+                None => {
+                    object_bounds.push((set_name.clone(), (f64::NEG_INFINITY, f64::INFINITY)));
+                    object_bounds.len() - 1
+                }
+            };
+
+            let (lower, upper) = &mut object_bounds[index].1;
+            match bound_type {
+                BoundType::Lo => *lower = *value,
+                _ => *upper = *value,
+            }
+        }
+
+        let rows = if !rows.is_empty() {
             rows
         } else {
             entries
                 .iter()
                 .map(|((row_name, _), _)| (row_name.clone(), RowType::N))
-                .into_iter()
                 .collect()
         };
 
-        let cols = if cols.len() > 0 {
+        let cols = if !cols.is_empty() {
             cols
         } else {
             entries
                 .iter()
                 .map(|((_, col_name), _)| (col_name.clone(), ColumnType::__))
-                .into_iter()
                 .collect()
         };
 
-        SIF {
+        // The maps above are sorted by name, so keep the file order of rows and
+        // columns separately; without declarations it is the order of the
+        // entries.
+        let in_file_order = |declared: Vec<&String>, used: Vec<&String>| {
+            let names = if declared.is_empty() { used } else { declared };
+            let mut seen = HashSet::new();
+            names
+                .into_iter()
+                .filter(|name| seen.insert(*name))
+                .cloned()
+                .collect::<Vec<String>>()
+        };
+        let row_names = in_file_order(
+            parser.rows.iter().map(|(name, _)| name).collect(),
+            parser
+                .entries
+                .iter()
+                .map(|(row_name, _, _)| row_name)
+                .collect(),
+        );
+        let col_names = in_file_order(
+            parser.cols.iter().map(|(name, _)| name).collect(),
+            parser
+                .entries
+                .iter()
+                .map(|(_, col_name, _)| col_name)
+                .collect(),
+        );
+        let index = |names: &[String]| {
+            names
+                .iter()
+                .enumerate()
+                .map(|(position, name)| (name.clone(), position))
+                .collect::<HashMap<String, usize>>()
+        };
+        let row_index = index(&row_names);
+        let col_index = index(&col_names);
+
+        // Sum the quadratic terms into the lower triangle of Q.
+        let mut quadratic_matrix: BTreeMap<(usize, usize), f64> = BTreeMap::new();
+        for (col_name_i, col_name_j, coeff) in &parser.quadratic_terms {
+            let (i, j) = (col_index[col_name_i], col_index[col_name_j]);
+            *quadratic_matrix.entry((i.min(j), i.max(j))).or_default() += coeff;
+        }
+        let quadratic_matrix = quadratic_matrix
+            .into_iter()
+            .map(|((col, row), coeff)| (row, col, coeff))
+            .collect();
+
+        let mut sif = SIF {
             name: parser.name.clone(),
             rows,
+            declared_cols: cols.clone(),
             cols,
+            row_names,
+            col_names,
+            row_index,
+            col_index,
             entries,
-            rhs,
-            // ranges: parser.ranges.clone(),
-            bounds,
-            // start_point: parser.start_point.clone(),
+            row_scales,
+            rhs_sets,
+            rhs: BTreeMap::new(),
+            objective_offset: 0.0,
+            range_sets,
+            ranges: BTreeMap::new(),
+            bound_records,
+            bounds: BTreeMap::new(),
+            active_rhs: None,
+            active_ranges: None,
+            active_bounds: None,
+            start_points,
+            active_start_point: None,
             quadratic,
-        }
+            quadratic_matrix,
+            object_bounds,
+            objective_sense: parser.objective_sense,
+            objective_name: parser.objective_name.clone(),
+            parameters,
+            parameter_choices: parser.parameter_choices.clone(),
+            element_types: parser
+                .element_types
+                .iter()
+                .map(|element_type| (element_type.name.clone(), element_type.clone()))
+                .collect(),
+            element_uses: parser
+                .element_uses
+                .iter()
+                .map(|element| (element.name.clone(), element.clone()))
+                .collect(),
+            element_functions: parser
+                .element_functions
+                .iter()
+                .map(|function| (function.get_name().to_string(), function.clone()))
+                .collect(),
+            group_types: parser
+                .group_types
+                .iter()
+                .map(|group_type| (group_type.name.clone(), group_type.clone()))
+                .collect(),
+            group_uses: parser
+                .group_uses
+                .iter()
+                .map(|group| (group.name.clone(), group.clone()))
+                .collect(),
+            group_functions: parser
+                .group_functions
+                .iter()
+                .map(|function| (function.get_name().to_string(), function.clone()))
+                .collect(),
+        };
+
+        // As MPS tools do, the first set of each kind is active by default.
+        sif.active_rhs = sif.rhs_sets.first().map(|(name, _)| name.clone());
+        sif.active_ranges = sif.range_sets.first().map(|(name, _)| name.clone());
+        sif.active_bounds = sif.bound_records.first().map(|(name, ..)| name.clone());
+        sif.active_start_point = sif.start_points.first().map(|(name, _)| name.clone());
+        sif.resolve_sets();
+
+        sif
     }
 }
 
@@ -621,26 +2165,76 @@ pub struct SIF {
 
     /// Row (constraint) definitions mapped by name.
     rows: BTreeMap<String, RowType>,
-    /// Column (variable) definitions mapped by name.
+    /// Column types as declared in the `COLUMNS`/`VARIABLES` section.
+    declared_cols: BTreeMap<String, ColumnType>,
+    /// Column (variable) definitions mapped by name, with the integrality
+    /// implied by the active bound set.
     cols: BTreeMap<String, ColumnType>,
+    /// Row names in file order.
+    row_names: Vec<String>,
+    /// Column names in file order.
+    col_names: Vec<String>,
+    /// Position of each row in `row_names`.
+    row_index: HashMap<String, usize>,
+    /// Position of each column in `col_names`.
+    col_index: HashMap<String, usize>,
     /// Non-zero matrix entries keyed by `(row_name, col_name)`.
     entries: BTreeMap<(String, String), f64>,
+    /// Row scale factors keyed by row name.
+    row_scales: BTreeMap<String, f64>,
 
-    /// Right-hand side values keyed by row name.
+    /// Named right-hand side sets in file order.
+    rhs_sets: Vec<(String, BTreeMap<String, f64>)>,
+    /// Right-hand side values of the active set keyed by row name, excluding
+    /// free rows.
     rhs: BTreeMap<String, f64>,
-    /// Range values for constraints: `(row_name, value)`.
-    // ranges: BTreeMap<String, f64>,
-    /// Variable bounds keyed by column name.
-    bounds: BTreeMap<String, (BoundType, f64)>,
-    /// Warm-start values: `(col_name, value)`.
-    // start_point: BTreeMap<String, f64>,
+    /// Objective constant given by the right-hand side of the free rows.
+    objective_offset: f64,
+    /// Named range sets in file order.
+    range_sets: Vec<(String, BTreeMap<String, f64>)>,
+    /// Range values of the active set keyed by row name.
+    ranges: BTreeMap<String, f64>,
+    /// Raw bound records in file order: `(bound_name, type, col_name, value)`.
+    bound_records: Vec<(String, BoundType, String, f64)>,
+    /// Effective `(lower, upper)` bounds of the active set keyed by column name.
+    bounds: BTreeMap<String, (f64, f64)>,
+    /// Names of the active right-hand side, range and bound sets.
+    active_rhs: Option<String>,
+    active_ranges: Option<String>,
+    active_bounds: Option<String>,
+    /// Named start points in file order, each keyed by column name (including
+    /// the `'DEFAULT'` value, if given).
+    start_points: Vec<(String, BTreeMap<String, f64>)>,
+    /// Name of the active start point.
+    active_start_point: Option<String>,
     /// Quadratic objective terms keyed by `(col_name_i, col_name_j)`.
     quadratic: BTreeMap<(String, String), f64>,
-    // element_type: String,
-    // element_uses: Vec<String>,
-    // group_type: String,
-    // group_uses: Vec<String>,
-    // object_bounds: Vec<(String, String)>,
+    /// Lower triangle of the symmetric matrix Q of `½ xᵀQx` as `(row, col, q)`
+    /// triplets in file order, sorted by column and then row.
+    quadratic_matrix: Vec<(usize, usize, f64)>,
+    /// Nonlinear element types keyed by name.
+    element_types: BTreeMap<String, ElementType>,
+    /// Nonlinear elements keyed by name.
+    element_uses: BTreeMap<String, ElementUse>,
+    /// Element functions from the `ELEMENTS` section keyed by element type.
+    element_functions: BTreeMap<String, ElementFunction>,
+    /// Nonlinear group types keyed by name.
+    group_types: BTreeMap<String, GroupType>,
+    /// Nonlinear parts of the groups keyed by row name.
+    group_uses: BTreeMap<String, GroupUse>,
+    /// Group functions from the `GROUPS` function section keyed by group type.
+    group_functions: BTreeMap<String, GroupFunction>,
+    /// Known `(lower, upper)` bounds on the objective value, one entry per
+    /// named bound set in file order.
+    object_bounds: Vec<(String, (f64, f64))>,
+    /// Whether the objective is minimized or maximized.
+    objective_sense: ObjSense,
+    /// Row holding the objective, if the problem has a free row.
+    objective_name: Option<String>,
+    /// Final values of the parameters defined in the file.
+    parameters: BTreeMap<String, f64>,
+    /// Values offered for each `$-PARAMETER`, in file order.
+    parameter_choices: BTreeMap<String, Vec<f64>>,
 }
 
 impl SIF {
@@ -650,12 +2244,86 @@ impl SIF {
         SIF {
             name: String::new(),
             rows: BTreeMap::new(),
+            declared_cols: BTreeMap::new(),
             cols: BTreeMap::new(),
+            row_names: Vec::new(),
+            col_names: Vec::new(),
+            row_index: HashMap::new(),
+            col_index: HashMap::new(),
             entries: BTreeMap::new(),
+            row_scales: BTreeMap::new(),
+            rhs_sets: Vec::new(),
             rhs: BTreeMap::new(),
-            // ranges: BTreeMap::new(),
+            objective_offset: 0.0,
+            range_sets: Vec::new(),
+            ranges: BTreeMap::new(),
+            bound_records: Vec::new(),
             bounds: BTreeMap::new(),
+            active_rhs: None,
+            active_ranges: None,
+            active_bounds: None,
+            start_points: Vec::new(),
+            active_start_point: None,
             quadratic: BTreeMap::new(),
+            quadratic_matrix: Vec::new(),
+            object_bounds: Vec::new(),
+            objective_sense: ObjSense::Min,
+            objective_name: None,
+            parameters: BTreeMap::new(),
+            parameter_choices: BTreeMap::new(),
+            element_types: BTreeMap::new(),
+            element_uses: BTreeMap::new(),
+            element_functions: BTreeMap::new(),
+            group_types: BTreeMap::new(),
+            group_uses: BTreeMap::new(),
+            group_functions: BTreeMap::new(),
+        }
+    }
+
+    /// Rebuilds the `rhs`, `ranges` and `bounds` views from the active sets.
+    fn resolve_sets(&mut self) {
+        let find = |sets: &[(String, BTreeMap<String, f64>)], active: &Option<String>| {
+            sets.iter()
+                .find(|(name, _)| Some(name) == active.as_ref())
+                .map(|(_, set)| set.clone())
+                .unwrap_or_default()
+        };
+
+        // A right-hand side on the objective row is the negated objective
+        // constant, and one on another free row is no constraint bound either.
+        let (objective, rhs): (BTreeMap<String, f64>, BTreeMap<String, f64>) =
+            find(&self.rhs_sets, &self.active_rhs)
+                .into_iter()
+                .partition(|(row_name, _)| self.rows.get(row_name) == Some(&RowType::N));
+        self.objective_offset = self
+            .objective_name
+            .as_ref()
+            .and_then(|name| objective.get(name))
+            .map_or(0.0, |value| -self.objective_sign() * value);
+        self.rhs = rhs;
+        self.ranges = find(&self.range_sets, &self.active_ranges);
+
+        let records: Vec<_> = self
+            .bound_records
+            .iter()
+            .filter(|(name, ..)| Some(name) == self.active_bounds.as_ref())
+            .cloned()
+            .collect();
+        self.bounds = resolve_bounds(&records);
+
+        // Integer bound types imply the integrality of their column.
+        self.cols = self.declared_cols.clone();
+        for (_, bound_type, col_name, _) in &records {
+            let implied = match bound_type {
+                BoundType::Bv => ColumnType::Z,
+                BoundType::Li | BoundType::Ui => ColumnType::X,
+                _ => continue,
+            };
+            if let Some(col_type) = self.cols.get_mut(col_name) {
+                if *col_type != ColumnType::Z {
+                    *col_type = implied;
+                }
+            }
         }
     }
 
@@ -671,21 +2339,609 @@ impl SIF {
         &self.cols
     }
 
+    /// Returns the row names in file order.
+    ///
+    /// Unlike the maps returned by [`SIF::get_rows`] and friends, which are
+    /// sorted by name, this order is the one the problem was written in and
+    /// that solvers and reference solutions number the rows by.
+    pub fn get_row_names(&self) -> &Vec<String> {
+        &self.row_names
+    }
+
+    /// Returns the column names in file order.
+    pub fn get_col_names(&self) -> &Vec<String> {
+        &self.col_names
+    }
+
+    /// Returns the position of the row `name` in [`SIF::get_row_names`].
+    pub fn get_row_index(&self, name: &str) -> Option<usize> {
+        self.row_index.get(name).copied()
+    }
+
+    /// Returns the position of the column `name` in [`SIF::get_col_names`].
+    pub fn get_col_index(&self, name: &str) -> Option<usize> {
+        self.col_index.get(name).copied()
+    }
+
+    /// Returns the name of the row at position `index` in file order.
+    pub fn get_row_name(&self, index: usize) -> Option<&str> {
+        self.row_names.get(index).map(String::as_str)
+    }
+
+    /// Returns the name of the column at position `index` in file order.
+    pub fn get_col_name(&self, index: usize) -> Option<&str> {
+        self.col_names.get(index).map(String::as_str)
+    }
+
+    /// Returns the names of the integer (`X`) columns, excluding binary ones.
+    ///
+    /// Columns with an `LI` or `UI` record in the active bound set are
+    /// integer as well, and columns with a `BV` record are binary.
+    pub fn get_integer_cols(&self) -> Vec<&str> {
+        self.cols
+            .iter()
+            .filter(|(_, col_type)| **col_type == ColumnType::X)
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Returns the names of the binary (`Z`) columns.
+    pub fn get_binary_cols(&self) -> Vec<&str> {
+        self.cols
+            .iter()
+            .filter(|(_, col_type)| **col_type == ColumnType::Z)
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
     pub fn get_entries(&self) -> &BTreeMap<(String, String), f64> {
         &self.entries
     }
 
+    /// Returns the names of the rows of the constraint matrix: the non-free
+    /// rows in file order, which are also the constraints of the
+    /// [`evaluator`].
+    ///
+    /// Free rows, the objective row (see [`SIF::get_objective_name`]) and any
+    /// other, are left out.
+    pub fn get_constraint_row_names(&self) -> Vec<&str> {
+        self.row_names
+            .iter()
+            .filter(|name| self.rows[*name] != RowType::N)
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Returns the entries of the constraint matrix as `(row, col, value)`
+    /// triplets, sorted by column and then row.
+    ///
+    /// Row indices refer to [`SIF::get_constraint_row_names`] and column
+    /// indices to [`SIF::get_col_names`]; entries of free rows are left out.
+    pub fn get_constraint_matrix_coo(&self) -> Vec<(usize, usize, f64)> {
+        let row_index: HashMap<&str, usize> = self
+            .get_constraint_row_names()
+            .into_iter()
+            .enumerate()
+            .map(|(position, name)| (name, position))
+            .collect();
+
+        let mut triplets: Vec<(usize, usize, f64)> = self
+            .entries
+            .iter()
+            .filter_map(|((row_name, col_name), value)| {
+                let row = *row_index.get(row_name.as_str())?;
+                Some((row, self.col_index[col_name], *value))
+            })
+            .collect();
+        triplets.sort_by_key(|(row, col, _)| (*col, *row));
+        triplets
+    }
+
+    /// Returns the constraint matrix in compressed sparse column form, with
+    /// the orderings of [`SIF::get_constraint_matrix_coo`].
+    pub fn get_constraint_matrix_csc(&self) -> CscMatrix {
+        CscMatrix::from_triplets(
+            self.get_constraint_row_names().len(),
+            self.col_names.len(),
+            &self.get_constraint_matrix_coo(),
+        )
+    }
+
+    /// Returns the constraint matrix in compressed sparse row form, with the
+    /// orderings of [`SIF::get_constraint_matrix_coo`].
+    pub fn get_constraint_matrix_csr(&self) -> CsrMatrix {
+        CsrMatrix::from_triplets(
+            self.get_constraint_row_names().len(),
+            self.col_names.len(),
+            &self.get_constraint_matrix_coo(),
+        )
+    }
+
+    /// Returns the dense linear objective vector `c`, the entries of the
+    /// objective row ordered like [`SIF::get_col_names`].
+    ///
+    /// For a maximization problem the vector is negated, so that the problem
+    /// always reads: minimize `cᵀx`. The constant of
+    /// [`SIF::get_objective_offset`] is not included.
+    pub fn get_objective_vector(&self) -> Vec<f64> {
+        let sign = self.objective_sign();
+        let mut objective = vec![0.0; self.col_names.len()];
+        if let Some(objective_name) = &self.objective_name {
+            for ((row_name, col_name), value) in &self.entries {
+                if row_name == objective_name {
+                    objective[self.col_index[col_name]] = sign * value;
+                }
+            }
+        }
+        objective
+    }
+
+    /// Returns the scale factors given with `'SCALE'` in the `ROWS`/`GROUPS`
+    /// section, keyed by row name. The value of a scaled row is divided by its
+    /// factor; rows without an entry are unscaled.
+    pub fn get_row_scales(&self) -> &BTreeMap<String, f64> {
+        &self.row_scales
+    }
+
+    /// Returns the right-hand side values of the active set keyed by row name.
+    ///
+    /// Free (`N`) rows are not included: their right-hand side is the
+    /// objective constant reported by [`SIF::get_objective_offset`].
     pub fn get_rhs(&self) -> &BTreeMap<String, f64> {
         &self.rhs
     }
 
-    pub fn get_bounds(&self) -> &BTreeMap<String, (BoundType, f64)> {
+    /// Returns the constant term of the objective in the active right-hand
+    /// side set.
+    ///
+    /// Only the right-hand side of the objective row (see
+    /// [`SIF::get_objective_name`]) counts.
+    ///
+    /// As in MPS and SIF, a right-hand side `b` on the objective row moves to
+    /// the other side of `obj = cᵀx - b`, so the objective reads
+    /// `cᵀx + offset` with `offset = -b`.
+    ///
+    /// For a maximization problem the offset is negated to `b`, like
+    /// [`SIF::get_objective_vector`] and [`SIF::get_quadratic_coo`], so that
+    /// the problem always reads: minimize `cᵀx + ½ xᵀQx + offset`, the value
+    /// of [`crate::evaluator::Evaluator::obj`].
+    pub fn get_objective_offset(&self) -> f64 {
+        self.objective_offset
+    }
+
+    /// `1` for a minimization problem and `-1` for a maximization problem,
+    /// the factor applied to the objective data to present it as a
+    /// minimization.
+    fn objective_sign(&self) -> f64 {
+        match self.objective_sense {
+            ObjSense::Min => 1.0,
+            ObjSense::Max => -1.0,
+        }
+    }
+
+    /// Returns the right-hand side of any row in the active set, including
+    /// free rows, i.e. the constant of its group.
+    pub(crate) fn get_row_constant(&self, row_name: &str) -> f64 {
+        self.rhs_sets
+            .iter()
+            .find(|(name, _)| Some(name) == self.active_rhs.as_ref())
+            .and_then(|(_, set)| set.get(row_name))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Returns the names of all right-hand side sets in file order.
+    pub fn get_rhs_names(&self) -> Vec<&str> {
+        self.rhs_sets
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Returns the name of the active right-hand side set, if any.
+    pub fn get_active_rhs(&self) -> Option<&str> {
+        self.active_rhs.as_deref()
+    }
+
+    /// Makes the named right-hand side set the active one.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if no set with that name exists.
+    pub fn select_rhs(&mut self, name: &str) -> Result<(), ParseError> {
+        if !self.rhs_sets.iter().any(|(set_name, _)| set_name == name) {
+            return Err(ParseError {
+                message: format!("Unknown RHS set: {}", name),
+            });
+        }
+
+        self.active_rhs = Some(name.to_string());
+        self.resolve_sets();
+        Ok(())
+    }
+
+    /// Returns the range values of the active set keyed by row name.
+    ///
+    /// A range `R` turns a single-sided or equality row into a two-sided
+    /// constraint `lo <= row <= hi`, where `rhs` is the row's right-hand side:
+    ///
+    /// | Row type | `R` sign | `lo`        | `hi`        |
+    /// |----------|----------|-------------|-------------|
+    /// | `G`      | any      | `rhs`       | `rhs + |R|` |
+    /// | `L`      | any      | `rhs - |R|` | `rhs`       |
+    /// | `E`      | `+`      | `rhs`       | `rhs + |R|` |
+    /// | `E`      | `-`      | `rhs - |R|` | `rhs`       |
+    ///
+    /// Ranges on free (`N`) rows have no meaning and are not included.
+    pub fn get_ranges(&self) -> &BTreeMap<String, f64> {
+        &self.ranges
+    }
+
+    /// Returns the names of all range sets in file order.
+    pub fn get_range_names(&self) -> Vec<&str> {
+        self.range_sets
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Returns the name of the active range set, if any.
+    pub fn get_active_ranges(&self) -> Option<&str> {
+        self.active_ranges.as_deref()
+    }
+
+    /// Makes the named range set the active one.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if no set with that name exists.
+    pub fn select_ranges(&mut self, name: &str) -> Result<(), ParseError> {
+        if !self.range_sets.iter().any(|(set_name, _)| set_name == name) {
+            return Err(ParseError {
+                message: format!("Unknown RANGES set: {}", name),
+            });
+        }
+
+        self.active_ranges = Some(name.to_string());
+        self.resolve_sets();
+        Ok(())
+    }
+
+    /// Returns the resolved `(lower, upper)` interval of every non-free row,
+    /// so that each constraint reads `lower <= row <= upper`.
+    ///
+    /// Missing right-hand sides default to zero and ranges are applied as
+    /// described in [`SIF::get_ranges`]. Unbounded sides are reported as
+    /// `f64::NEG_INFINITY` / `f64::INFINITY`. Free (`N`) rows are omitted.
+    pub fn get_constraint_bounds(&self) -> BTreeMap<String, (f64, f64)> {
+        self.rows
+            .iter()
+            .filter(|(_, row_type)| **row_type != RowType::N)
+            .map(|(name, row_type)| {
+                let rhs = self.rhs.get(name).copied().unwrap_or(0.0);
+                let range = self.ranges.get(name).copied();
+
+                let bounds = match (row_type.base(), range) {
+                    (RowType::G, None) => (rhs, f64::INFINITY),
+                    (RowType::G, Some(r)) => (rhs, rhs + r.abs()),
+                    (RowType::L, None) => (f64::NEG_INFINITY, rhs),
+                    (RowType::L, Some(r)) => (rhs - r.abs(), rhs),
+                    (RowType::E, None) => (rhs, rhs),
+                    (RowType::E, Some(r)) if r >= 0.0 => (rhs, rhs + r),
+                    (RowType::E, Some(r)) => (rhs + r, rhs),
+                    _ => (f64::NEG_INFINITY, f64::INFINITY),
+                };
+
+                (name.clone(), bounds)
+            })
+            .collect()
+    }
+
+    /// Returns the raw bound records of all sets in file order as
+    /// `(bound_name, bound_type, col_name, value)`.
+    pub fn get_bound_records(&self) -> &Vec<(String, BoundType, String, f64)> {
+        &self.bound_records
+    }
+
+    /// Returns the names of all bound sets in file order.
+    pub fn get_bound_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::new();
+        for (name, ..) in &self.bound_records {
+            if !names.contains(&name.as_str()) {
+                names.push(name);
+            }
+        }
+        names
+    }
+
+    /// Returns the name of the active bound set, if any.
+    pub fn get_active_bounds(&self) -> Option<&str> {
+        self.active_bounds.as_deref()
+    }
+
+    /// Makes the named bound set the active one.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if no set with that name exists.
+    pub fn select_bounds(&mut self, name: &str) -> Result<(), ParseError> {
+        if !self
+            .bound_records
+            .iter()
+            .any(|(set_name, ..)| set_name == name)
+        {
+            return Err(ParseError {
+                message: format!("Unknown BOUNDS set: {}", name),
+            });
+        }
+
+        self.active_bounds = Some(name.to_string());
+        self.resolve_sets();
+        Ok(())
+    }
+
+    /// Returns the effective `(lower, upper)` bounds of every column that has
+    /// at least one bound record in the active set.
+    ///
+    /// All records of a column are combined in file order starting from the
+    /// default `[0, +inf)`; see [`SIF::get_column_bounds`] for columns without
+    /// records.
+    pub fn get_bounds(&self) -> &BTreeMap<String, (f64, f64)> {
         &self.bounds
     }
 
+    /// Returns the names of the semi-continuous columns, i.e. those with an
+    /// `SC` record in the active bound set.
+    ///
+    /// Such a column takes either the value zero or a value within the bounds
+    /// reported by [`SIF::get_bounds`].
+    pub fn get_semicontinuous_cols(&self) -> Vec<&str> {
+        let mut cols: Vec<&str> = Vec::new();
+        for (set_name, bound_type, col_name, _) in &self.bound_records {
+            if *bound_type == BoundType::Sc
+                && Some(set_name) == self.active_bounds.as_ref()
+                && !cols.contains(&col_name.as_str())
+            {
+                cols.push(col_name);
+            }
+        }
+        cols
+    }
+
+    /// Returns the effective `(lower, upper)` bounds of a column, or `None`
+    /// if the column does not exist.
+    ///
+    /// Columns without bound records default to `[0, +inf)`.
+    pub fn get_column_bounds(&self, col_name: &str) -> Option<(f64, f64)> {
+        if !self.cols.contains_key(col_name) {
+            return None;
+        }
+
+        Some(
+            self.bounds
+                .get(col_name)
+                .copied()
+                .unwrap_or((0.0, f64::INFINITY)),
+        )
+    }
+
+    /// Returns the names of all start points in file order.
+    pub fn get_start_point_names(&self) -> Vec<&str> {
+        self.start_points
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Returns the name of the active start point, if any.
+    pub fn get_active_start_point(&self) -> Option<&str> {
+        self.active_start_point.as_deref()
+    }
+
+    /// Makes the named start point the active one.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParseError`] if no start point with that name exists.
+    pub fn select_start_point(&mut self, name: &str) -> Result<(), ParseError> {
+        if !self
+            .start_points
+            .iter()
+            .any(|(set_name, _)| set_name == name)
+        {
+            return Err(ParseError {
+                message: format!("Unknown START POINT: {}", name),
+            });
+        }
+
+        self.active_start_point = Some(name.to_string());
+        Ok(())
+    }
+
+    /// Returns the active start point as a dense vector ordered like
+    /// [`SIF::get_col_names`].
+    ///
+    /// Columns without an explicit value take the start point's `'DEFAULT'`
+    /// value, or zero if none is given (also when the file has no
+    /// `START POINT` section).
+    pub fn get_start_point(&self) -> Vec<f64> {
+        let values = self
+            .start_points
+            .iter()
+            .find(|(name, _)| Some(name) == self.active_start_point.as_ref())
+            .map(|(_, values)| values);
+        let default = values
+            .and_then(|values| values.get("'DEFAULT'"))
+            .copied()
+            .unwrap_or(0.0);
+
+        self.col_names
+            .iter()
+            .map(|col_name| {
+                values
+                    .and_then(|values| values.get(col_name))
+                    .copied()
+                    .unwrap_or(default)
+            })
+            .collect()
+    }
+
+    /// Returns the known `(lower, upper)` bounds on the objective value, one
+    /// entry per named bound set in file order.
+    ///
+    /// Sides that are not given are reported as `f64::NEG_INFINITY` /
+    /// `f64::INFINITY`.
+    pub fn get_object_bounds(&self) -> &Vec<(String, (f64, f64))> {
+        &self.object_bounds
+    }
+
+    /// Returns whether the objective is minimized or maximized, as given by
+    /// an `OBJSENSE` card (minimization by default).
+    ///
+    /// The objective data itself is stored as written; derived objectives
+    /// such as [`evaluator::Evaluator::obj`] negate it for maximization.
+    pub fn get_objective_sense(&self) -> ObjSense {
+        self.objective_sense
+    }
+
+    /// Returns the name of the objective row, or `None` if the problem has
+    /// no free row.
+    ///
+    /// This is the row chosen with [`ParseOptions::with_objective_row`] or
+    /// named by an `OBJNAME` card, and otherwise the first free row in file
+    /// order, as in MPS. The other free rows are kept unless
+    /// [`ParseOptions::with_drop_free_rows`] is set, but the [`evaluator`]
+    /// uses this row alone as the objective.
+    pub fn get_objective_name(&self) -> Option<&str> {
+        self.objective_name.as_deref()
+    }
+
+    /// Returns the parameters defined in the file (`IE`, `RE`, `RM`, ...)
+    /// with their final values.
+    ///
+    /// Parameters are evaluated in file order and stay visible to all later
+    /// sections; integer parameters are reported as whole numbers.
+    pub fn get_parameters(&self) -> &BTreeMap<String, f64> {
+        &self.parameters
+    }
+
+    /// Returns the values offered for each `$-PARAMETER` in the file, in file
+    /// order, including the commented-out alternatives.
+    ///
+    /// The value actually used is reported by [`SIF::get_parameters`]; it can
+    /// be changed with [`ParseOptions::with_parameter`].
+    pub fn get_parameter_choices(&self) -> &BTreeMap<String, Vec<f64>> {
+        &self.parameter_choices
+    }
+
+    /// Returns the nonlinear element types declared in the `ELEMENT TYPE`
+    /// section, keyed by name.
+    pub fn get_element_types(&self) -> &BTreeMap<String, ElementType> {
+        &self.element_types
+    }
+
+    /// Returns the nonlinear elements declared in the `ELEMENT USES`
+    /// section, keyed by name, with their variables and parameters bound.
+    pub fn get_element_uses(&self) -> &BTreeMap<String, ElementUse> {
+        &self.element_uses
+    }
+
+    /// Returns the element functions defined in the `ELEMENTS` section,
+    /// keyed by element type.
+    pub fn get_element_functions(&self) -> &BTreeMap<String, ElementFunction> {
+        &self.element_functions
+    }
+
+    /// Returns the nonlinear group types declared in the `GROUP TYPE`
+    /// section, keyed by name.
+    pub fn get_group_types(&self) -> &BTreeMap<String, GroupType> {
+        &self.group_types
+    }
+
+    /// Returns the nonlinear parts of the groups declared in the
+    /// `GROUP USES` section (and of every group when a `'DEFAULT'` group
+    /// type is given), keyed by row name.
+    pub fn get_group_uses(&self) -> &BTreeMap<String, GroupUse> {
+        &self.group_uses
+    }
+
+    /// Returns the nonlinear part of the group of row `row_name`, if it has
+    /// one.
+    pub fn get_group_use(&self, row_name: &str) -> Option<&GroupUse> {
+        self.group_uses.get(row_name)
+    }
+
+    /// Returns the group functions defined in the `GROUPS` section that
+    /// follows the first `ENDATA`, keyed by group type.
+    pub fn get_group_functions(&self) -> &BTreeMap<String, GroupFunction> {
+        &self.group_functions
+    }
+
+    /// Returns the quadratic objective terms as written, keyed by
+    /// `(col_name_i, col_name_j)`; terms repeated for the same pair are added
+    /// up.
+    ///
+    /// Whether a pair stands for one or both triangles depends on the section;
+    /// [`SIF::get_quadratic_coo`] gives the normalized matrix.
     pub fn get_quadratic(&self) -> &BTreeMap<(String, String), f64> {
         &self.quadratic
     }
+
+    /// Returns the lower triangle (`row >= col`) of the symmetric matrix Q of
+    /// the quadratic objective `½ xᵀQx` as `(row, col, q)` triplets, sorted by
+    /// column and then row, with indices referring to [`SIF::get_col_names`].
+    ///
+    /// In `QUADRATIC`, `HESSIAN`, `QUADS`, `QUADOBJ` and `QSECTION` sections
+    /// each off-diagonal entry is written once, in either triangle, and sets
+    /// both `Q_ij` and `Q_ji`; entries for the same pair add up. A `QMATRIX`
+    /// section lists both triangles, so each of its off-diagonal entries
+    /// counts for half.
+    ///
+    /// As a convention of this crate, not of the SIF format, Q is negated for
+    /// a maximization problem, like [`SIF::get_objective_vector`] and
+    /// [`SIF::get_objective_offset`], so that the problem always reads as a
+    /// minimization.
+    pub fn get_quadratic_coo(&self) -> Vec<(usize, usize, f64)> {
+        let sign = self.objective_sign();
+        self.quadratic_matrix
+            .iter()
+            .map(|(row, col, coeff)| (*row, *col, sign * coeff))
+            .collect()
+    }
+
+    /// Returns the lower triangle of Q (see [`SIF::get_quadratic_coo`]) in
+    /// compressed sparse column form.
+    pub fn get_quadratic_csc_lower(&self) -> CscMatrix {
+        let n = self.col_names.len();
+        CscMatrix::from_triplets(n, n, &self.get_quadratic_coo())
+    }
+
+    /// Returns the upper triangle of Q (see [`SIF::get_quadratic_coo`]) in
+    /// compressed sparse column form.
+    pub fn get_quadratic_csc_upper(&self) -> CscMatrix {
+        let n = self.col_names.len();
+        let upper: Vec<(usize, usize, f64)> = self
+            .get_quadratic_coo()
+            .into_iter()
+            .map(|(row, col, coeff)| (col, row, coeff))
+            .collect();
+        CscMatrix::from_triplets(n, n, &upper)
+    }
+
+    /// Returns the full symmetric Q (see [`SIF::get_quadratic_coo`]) in
+    /// compressed sparse column form.
+    pub fn get_quadratic_csc(&self) -> CscMatrix {
+        let n = self.col_names.len();
+        let full: Vec<(usize, usize, f64)> = self
+            .get_quadratic_coo()
+            .into_iter()
+            .flat_map(|(row, col, coeff)| {
+                let mirror = (row != col).then_some((col, row, coeff));
+                std::iter::once((row, col, coeff)).chain(mirror)
+            })
+            .collect();
+        CscMatrix::from_triplets(n, n, &full)
+    }
 }
 
 /// Parses a SIF-formatted string into a [`SIF`] problem description.
@@ -702,7 +2958,64 @@ impl SIF {
 /// let sif = sif_rs::parse_sif(&input).unwrap();
 /// ```
 pub fn parse_sif(input: &str) -> Result<SIF, ParseError> {
-    SifParser::parse(input)
+    SifParser::parse(input, &ParseOptions::default())
+}
+
+/// Options controlling how a SIF input is parsed.
+///
+/// # Example
+///
+/// ```no_run
+/// let options = sif_rs::ParseOptions::default().with_parameter("N", 1000.0);
+/// let sif = sif_rs::parse_file_with("examples/PROBLEM.SIF", &options).unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    /// Values overriding `$-PARAMETER` definitions, keyed by parameter name.
+    parameters: BTreeMap<String, f64>,
+    /// Objective row overriding `OBJNAME` and the first free row.
+    objective_row: Option<String>,
+    /// Whether free rows other than the objective are removed.
+    drop_free_rows: bool,
+}
+
+impl ParseOptions {
+    /// Overrides the value of the `$-PARAMETER` named `name`, like
+    /// `sifdecoder -param NAME=value`.
+    ///
+    /// Parsing fails if the file has no `$-PARAMETER` of that name.
+    pub fn with_parameter(mut self, name: &str, value: f64) -> Self {
+        self.parameters.insert(name.to_string(), value);
+        self
+    }
+
+    /// Uses the free row named `name` as the objective, instead of the row
+    /// named by `OBJNAME` or the first free row.
+    ///
+    /// Parsing fails if the file has no free row of that name.
+    pub fn with_objective_row(mut self, name: &str) -> Self {
+        self.objective_row = Some(name.to_string());
+        self
+    }
+
+    /// Removes the free rows other than the objective, with their entries,
+    /// right-hand sides, ranges, scales and groups, as MPS readers do for
+    /// NETLIB problems. Free rows are kept by default.
+    pub fn with_drop_free_rows(mut self, drop: bool) -> Self {
+        self.drop_free_rows = drop;
+        self
+    }
+}
+
+/// Parses a SIF-formatted string into a [`SIF`] problem description using
+/// the given [`ParseOptions`].
+///
+/// # Errors
+///
+/// Returns a [`ParseError`] if the input cannot be parsed or if an option
+/// refers to a parameter that the input does not declare.
+pub fn parse_sif_with(input: &str, options: &ParseOptions) -> Result<SIF, ParseError> {
+    SifParser::parse(input, options)
 }
 
 /// Reads a SIF file from disk and parses it into a [`SIF`] problem description.
@@ -726,6 +3039,19 @@ pub fn parse_file(path: &str) -> Result<SIF, ParseError> {
     parse_sif(&input)
 }
 
+/// Reads a SIF file from disk and parses it using the given [`ParseOptions`].
+///
+/// # Errors
+///
+/// Returns a [`ParseError`] if the file cannot be read or if the content
+/// cannot be parsed.
+pub fn parse_file_with(path: &str, options: &ParseOptions) -> Result<SIF, ParseError> {
+    let input = std::fs::read_to_string(path).map_err(|e| ParseError {
+        message: format!("Failed to read file: {}", e),
+    })?;
+    parse_sif_with(&input, options)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -778,7 +3104,7 @@ mod tests {
         assert_eq!(sif.rhs.get("r1"), Some(&2.0));
         assert_eq!(sif.rhs.get("r2"), Some(&6.0));
 
-        assert_eq!(sif.bounds.get("c1"), Some(&(BoundType::Up, 20.0)));
+        assert_eq!(sif.bounds.get("c1"), Some(&(0.0, 20.0)));
 
         assert_eq!(
             sif.quadratic.get(&("c1".to_string(), "c1".to_string())),
@@ -831,6 +3157,1508 @@ mod tests {
         assert_eq!(sif.name, "DFL001");
     }
 
+    #[test]
+    fn test_ranges() {
+        let sif = parse_file("examples/BOEING1.SIF").unwrap();
+
+        assert_eq!(sif.name, "BOEING1");
+        assert_eq!(sif.ranges.len(), 89);
+        assert_eq!(sif.rows.get("DMBOSHNL"), Some(&RowType::L));
+        assert_eq!(sif.rhs.get("DMBOSHNL"), Some(&12.0));
+        assert_eq!(sif.ranges.get("DMBOSHNL"), Some(&2.0));
+
+        let sif = parse_file("examples/BOEING2.SIF").unwrap();
+        assert_eq!(sif.ranges.len(), 19);
+
+        let sif = parse_file("examples/NESM.SIF").unwrap();
+        assert_eq!(sif.ranges.len(), 88);
+
+        let sif = parse_file("examples/SEBA.SIF").unwrap();
+        assert_eq!(sif.ranges.len(), 7);
+        assert_eq!(sif.ranges.get("VILLKOR8"), Some(&7.5));
+    }
+
+    #[test]
+    fn test_constraint_bounds() {
+        let sif = parse_file("examples/qptest.sif").unwrap();
+        let bounds = sif.get_constraint_bounds();
+
+        assert_eq!(bounds.len(), 2);
+        assert_eq!(bounds.get("obj"), None);
+        assert_eq!(bounds.get("r1"), Some(&(2.0, f64::INFINITY)));
+        assert_eq!(bounds.get("r2"), Some(&(f64::NEG_INFINITY, 6.0)));
+
+        let sif = parse_file("examples/SEBA.SIF").unwrap();
+        let bounds = sif.get_constraint_bounds();
+
+        // G row with RHS 5 and range 7
+        assert_eq!(bounds.get("VILLKOR2"), Some(&(5.0, 12.0)));
+
+        let sif = parse_file("examples/BOEING1.SIF").unwrap();
+        let bounds = sif.get_constraint_bounds();
+
+        // L row with RHS 12 and range 2
+        assert_eq!(bounds.get("DMBOSHNL"), Some(&(10.0, 12.0)));
+        // E row with RHS 10.5 and no range
+        assert_eq!(bounds.get("FLAV*1"), Some(&(10.5, 10.5)));
+        // G row without RHS
+        assert_eq!(bounds.get("REVENUES"), Some(&(0.0, f64::INFINITY)));
+    }
+
+    #[test]
+    fn test_bounds() {
+        let sif = parse_file("examples/BOEING1.SIF").unwrap();
+
+        assert_eq!(sif.bounds.get("GRDTIMN1"), Some(&(-105.0, 0.0)));
+        assert_eq!(sif.get_column_bounds("GRDTIMN1"), Some((-105.0, 0.0)));
+        assert_eq!(
+            sif.get_column_bounds("PBOSHNL0"),
+            Some((0.0, f64::INFINITY))
+        );
+        assert_eq!(sif.get_column_bounds("UNKNOWN"), None);
+
+        let records = sif.get_bound_records();
+        assert_eq!(
+            records[0],
+            (
+                "INTBOU".to_string(),
+                BoundType::Lo,
+                "GRDTIMN1".to_string(),
+                -105.0
+            )
+        );
+        assert_eq!(
+            records[1],
+            (
+                "INTBOU".to_string(),
+                BoundType::Up,
+                "GRDTIMN1".to_string(),
+                0.0
+            )
+        );
+
+        let input = "NAME          BNDTEST
+ROWS
+ N  obj
+COLUMNS
+    x1        obj                1.0
+    x2        obj                1.0
+    x3        obj                1.0
+    x4        obj                1.0
+    x5        obj                1.0
+BOUNDS
+ UP bnd       x1                -4.0
+ LO bnd       x2                -1.0
+ UP bnd       x2                -0.5
+ MI bnd       x3
+ UP bnd       x3                 3.0
+ FR bnd       x4
+ FX bnd       x5                 2.5
+ENDATA
+";
+        let sif = parse_sif(input).unwrap();
+
+        assert_eq!(sif.bounds.get("x1"), Some(&(f64::NEG_INFINITY, -4.0)));
+        assert_eq!(sif.bounds.get("x2"), Some(&(-1.0, -0.5)));
+        assert_eq!(sif.bounds.get("x3"), Some(&(f64::NEG_INFINITY, 3.0)));
+        assert_eq!(
+            sif.bounds.get("x4"),
+            Some(&(f64::NEG_INFINITY, f64::INFINITY))
+        );
+        assert_eq!(sif.bounds.get("x5"), Some(&(2.5, 2.5)));
+    }
+
+    #[test]
+    fn test_named_sets() {
+        let input = "NAME          SETTEST
+ROWS
+ N  obj
+ G  r1
+ L  r2
+COLUMNS
+    x1        obj                1.0   r1                 1.0
+    x2        r1                 1.0   r2                 1.0
+RHS
+    rhs1      r1                 1.0   r2                 4.0
+    rhs2      r1                 2.0
+    rhs1      obj               -3.0
+RANGES
+    rng1      r1                 1.0
+    rng2      r2                 2.0
+BOUNDS
+ UP bnd1      x1                 5.0
+ LO bnd2      x1                 1.0
+ UP bnd2      x2                 2.0
+ENDATA
+";
+        let mut sif = parse_sif(input).unwrap();
+
+        assert_eq!(sif.get_rhs_names(), vec!["rhs1", "rhs2"]);
+        assert_eq!(sif.get_range_names(), vec!["rng1", "rng2"]);
+        assert_eq!(sif.get_bound_names(), vec!["bnd1", "bnd2"]);
+
+        assert_eq!(sif.get_active_rhs(), Some("rhs1"));
+        assert_eq!(sif.rhs.len(), 2);
+        assert_eq!(sif.rhs.get("r1"), Some(&1.0));
+        assert_eq!(sif.rhs.get("obj"), None);
+        assert_eq!(sif.get_objective_offset(), 3.0);
+        assert_eq!(sif.ranges.get("r1"), Some(&1.0));
+        assert_eq!(sif.ranges.get("r2"), None);
+        assert_eq!(sif.bounds.get("x1"), Some(&(0.0, 5.0)));
+        assert_eq!(sif.bounds.get("x2"), None);
+
+        sif.select_rhs("rhs2").unwrap();
+        sif.select_ranges("rng2").unwrap();
+        sif.select_bounds("bnd2").unwrap();
+
+        assert_eq!(sif.rhs.len(), 1);
+        assert_eq!(sif.rhs.get("r1"), Some(&2.0));
+        assert_eq!(sif.get_objective_offset(), 0.0);
+        assert_eq!(sif.ranges.get("r1"), None);
+        assert_eq!(sif.ranges.get("r2"), Some(&2.0));
+        assert_eq!(sif.bounds.get("x1"), Some(&(1.0, f64::INFINITY)));
+        assert_eq!(sif.bounds.get("x2"), Some(&(0.0, 2.0)));
+
+        let bounds = sif.get_constraint_bounds();
+        assert_eq!(bounds.get("r1"), Some(&(2.0, f64::INFINITY)));
+        assert_eq!(bounds.get("r2"), Some(&(-2.0, 0.0)));
+
+        assert!(sif.select_rhs("missing").is_err());
+        assert_eq!(sif.get_active_rhs(), Some("rhs2"));
+
+        let sif = parse_file("examples/SIERRA.SIF").unwrap();
+        assert_eq!(sif.get_bound_names(), vec![""]);
+    }
+
+    #[test]
+    fn test_column_major() {
+        let input = "NAME          COLTEST
+
+*   A small LP written with VARIABLES before GROUPS.
+
+VARIABLES
+
+    x1
+ X  x2
+    x3
+
+GROUPS
+
+ N  obj       x1                 1.0   x2                 2.0
+ N  obj       x3                -1.0
+ G  c1        x1                 1.0   x3                 1.0
+ L  c2        x2                 1.0
+
+*   A constraint without linear terms.
+
+ E  c3
+
+CONSTANTS
+
+    rhs       c1                 1.0   c2                 4.0
+
+ENDATA
+";
+        let sif = parse_sif(input).unwrap();
+
+        assert_eq!(sif.name, "COLTEST");
+        assert_eq!(sif.cols.len(), 3);
+        assert_eq!(sif.cols.get("x1"), Some(&ColumnType::__));
+        assert_eq!(sif.cols.get("x2"), Some(&ColumnType::X));
+
+        assert_eq!(sif.rows.len(), 4);
+        assert_eq!(sif.rows.get("obj"), Some(&RowType::N));
+        assert_eq!(sif.rows.get("c1"), Some(&RowType::G));
+        assert_eq!(sif.rows.get("c2"), Some(&RowType::L));
+        assert_eq!(sif.rows.get("c3"), Some(&RowType::E));
+
+        assert_eq!(sif.entries.len(), 6);
+        assert_eq!(
+            sif.entries.get(&("obj".to_string(), "x2".to_string())),
+            Some(&2.0)
+        );
+        assert_eq!(
+            sif.entries.get(&("obj".to_string(), "x3".to_string())),
+            Some(&-1.0)
+        );
+        assert_eq!(
+            sif.entries.get(&("c1".to_string(), "x3".to_string())),
+            Some(&1.0)
+        );
+
+        assert_eq!(sif.rhs.get("c1"), Some(&1.0));
+        assert_eq!(sif.rhs.get("c2"), Some(&4.0));
+    }
+
+    #[test]
+    fn test_start_point() {
+        let input = "NAME          STARTTEST
+VARIABLES
+    x1
+    x2
+    x3
+GROUPS
+ N  obj       x1                 1.0   x2                 1.0
+ E  c1        x3                 1.0
+START POINT
+    start1    'DEFAULT'          1.0
+    start1    x1                -1.2   c1                 0.5
+ V  start2    x2                 3.0
+ XV start2    x3                 4.0
+ M  start2    c1                 2.0
+ENDATA
+";
+        let mut sif = parse_sif(input).unwrap();
+
+        assert_eq!(sif.get_start_point_names(), vec!["start1", "start2"]);
+        assert_eq!(sif.get_active_start_point(), Some("start1"));
+        assert_eq!(sif.get_start_point(), vec![-1.2, 1.0, 1.0]);
+
+        sif.select_start_point("start2").unwrap();
+        assert_eq!(sif.get_start_point(), vec![0.0, 3.0, 4.0]);
+
+        assert!(sif.select_start_point("start3").is_err());
+
+        let sif = parse_file("examples/AFIRO.SIF").unwrap();
+        assert_eq!(sif.get_start_point(), vec![0.0; 32]);
+    }
+
+    #[test]
+    fn test_object_bounds() {
+        let input = "NAME          OBJBND
+VARIABLES
+    x1
+GROUPS
+ N  obj       x1                 1.0
+OBJECT BOUNDS
+ LO OBJLOWER  0.0
+ UP OBJBOTH   10.0
+ LO OBJBOTH   -5.0
+ENDATA
+";
+        let sif = parse_sif(input).unwrap();
+
+        assert_eq!(
+            sif.get_object_bounds(),
+            &vec![
+                ("OBJLOWER".to_string(), (0.0, f64::INFINITY)),
+                ("OBJBOTH".to_string(), (-5.0, 10.0)),
+            ]
+        );
+
+        let sif = parse_file("examples/AFIRO.SIF").unwrap();
+        assert!(sif.get_object_bounds().is_empty());
+    }
+
+    #[test]
+    fn test_integer_markers() {
+        let input = "NAME          MIPTEST
+ROWS
+ N  obj
+ L  c1
+COLUMNS
+    x1        obj                1.0   c1                 1.0
+    MARKER                 'MARKER'                 'INTORG'
+    x2        obj                2.0   c1                 1.0
+    x3        obj                3.0
+    x3        c1                 1.0
+    MARKER                 'MARKER'                 'INTEND'
+    x4        obj                1.0
+ X  x5        c1                 1.0
+ Z  x6        c1                 1.0
+RHS
+    rhs       c1                 4.0
+ENDATA
+";
+        let sif = parse_sif(input).unwrap();
+
+        assert_eq!(sif.cols.len(), 6);
+        assert_eq!(sif.cols.get("x1"), Some(&ColumnType::__));
+        assert_eq!(sif.cols.get("x2"), Some(&ColumnType::X));
+        assert_eq!(sif.cols.get("x4"), Some(&ColumnType::__));
+        assert_eq!(sif.get_integer_cols(), vec!["x2", "x3", "x5"]);
+        assert_eq!(sif.get_binary_cols(), vec!["x6"]);
+        assert_eq!(sif.entries.len(), 9);
+    }
+
+    #[test]
+    fn test_integer_bounds() {
+        let input = "NAME          MIPBND
+ROWS
+ N  obj
+COLUMNS
+    x1        obj                1.0
+    x2        obj                1.0
+    x3        obj                1.0
+    x4        obj                1.0
+    x5        obj                1.0
+BOUNDS
+ BV bnd       x1
+ LI bnd       x2                -3.0
+ UI bnd       x2                 7.0
+ UI bnd       x3                -2.0
+ SC bnd       x4                 5.0
+ LO bnd       x5                 1.0
+ SC bnd       x5                 0.0
+ENDATA
+";
+        let sif = parse_sif(input).unwrap();
+
+        assert_eq!(sif.bounds.get("x1"), Some(&(0.0, 1.0)));
+        assert_eq!(sif.bounds.get("x2"), Some(&(-3.0, 7.0)));
+        assert_eq!(sif.bounds.get("x3"), Some(&(f64::NEG_INFINITY, -2.0)));
+        assert_eq!(sif.bounds.get("x4"), Some(&(0.0, 5.0)));
+        assert_eq!(sif.bounds.get("x5"), Some(&(1.0, f64::INFINITY)));
+
+        assert_eq!(sif.get_binary_cols(), vec!["x1"]);
+        assert_eq!(sif.get_integer_cols(), vec!["x2", "x3"]);
+        assert_eq!(sif.get_semicontinuous_cols(), vec!["x4", "x5"]);
+
+        // Integrality follows the active bound set.
+        let mut sif = parse_sif(&input.replace(
+            "ENDATA",
+            " UP other    x1                 1.0\n LI other    x4                 0.0\nENDATA",
+        ))
+        .unwrap();
+        assert_eq!(sif.get_binary_cols(), vec!["x1"]);
+        sif.select_bounds("other").unwrap();
+        assert_eq!(sif.get_binary_cols(), Vec::<&str>::new());
+        assert_eq!(sif.get_integer_cols(), vec!["x4"]);
+        sif.select_bounds("bnd").unwrap();
+        assert_eq!(sif.get_integer_cols(), vec!["x2", "x3"]);
+    }
+
+    #[test]
+    fn test_extended_rows() {
+        let input = "NAME          XROWS
+VARIABLES
+    x1
+    x2
+GROUPS
+ N  obj       x1                 1.0   'SCALE'            2.0
+ XG c(1)      x1                 1.0   x2                 2.0
+ XL c(2)      x2                -1.0
+ DE d         c1                 2.0   c2                 3.0
+ DN dobj      obj               -1.0
+CONSTANTS
+    rhs       c1                 1.0
+ENDATA
+";
+        let sif = parse_sif(input).unwrap();
+
+        assert_eq!(sif.rows.len(), 5);
+        assert_eq!(sif.rows.get("c1"), Some(&RowType::G));
+        assert_eq!(sif.rows.get("c2"), Some(&RowType::L));
+        assert_eq!(sif.rows.get("d"), Some(&RowType::E));
+        assert_eq!(sif.rows.get("dobj"), Some(&RowType::N));
+
+        assert_eq!(
+            sif.entries.get(&("c1".to_string(), "x2".to_string())),
+            Some(&2.0)
+        );
+        // d = 2 * c1 + 3 * c2
+        assert_eq!(
+            sif.entries.get(&("d".to_string(), "x1".to_string())),
+            Some(&2.0)
+        );
+        assert_eq!(
+            sif.entries.get(&("d".to_string(), "x2".to_string())),
+            Some(&1.0)
+        );
+        assert_eq!(
+            sif.entries.get(&("dobj".to_string(), "x1".to_string())),
+            Some(&-1.0)
+        );
+        assert_eq!(sif.get_row_scales().get("obj"), Some(&2.0));
+        assert_eq!(sif.entries.len(), 7);
+
+        let input = "NAME          ZROWS
+VARIABLES
+    x1
+GROUPS
+ ZN obj       x1                       COEFF
+ENDATA
+";
+        assert!(parse_sif(input).is_err());
+
+        let input = "NAME          DROWS
+ROWS
+ N  obj
+ G  c1
+ DG d         c1                 2.0   obj                1.0
+COLUMNS
+    x1        obj                1.0   c1                 3.0
+ENDATA
+";
+        let sif = parse_sif(input).unwrap();
+
+        assert_eq!(sif.rows.get("d"), Some(&RowType::G));
+        assert_eq!(
+            sif.entries.get(&("d".to_string(), "x1".to_string())),
+            Some(&7.0)
+        );
+    }
+
+    #[test]
+    fn test_parameters() {
+        let input = "NAME          PARAMS
+
+*   Problem parameters
+
+ IE N                   3              $-PARAMETER
+ IE 1                   1
+ IA N-1       N         -1
+ IS 5-N       N          5
+ IM 2N        N          2
+ ID 6/N       N          6
+ I+ N+N       N                        N
+ RE H                   0.5D0
+ RI RN        N
+ RD 1/H       H          1.0
+ RS 1-H       H          1.0
+ RM 3H        H          3.0
+ RA H+1       H          1.0
+ R* H2        H                        H
+ R/ N/H       RN                       H
+ RF E         EXP        0.0
+ R( SQRT4     SQRT                     4.0
+ R( ABSH      ABS                      H
+ IR N/H-INT   N/H
+
+VARIABLES
+
+ X  X(1)
+ X  X(2)
+    X3
+
+GROUPS
+
+ N  OBJ       X3         1.0
+ XG C(1)      X(1)       1.0
+ ZL C(N-1)    X(2)                     1-H
+
+CONSTANTS
+
+ Z  RHS       C(1)                     3H
+ X  RHS       C(N-1)     2.5
+
+BOUNDS
+
+ XU BND       X(N-1)     4.0
+ ZL BND       X(1)                     1/H
+
+START POINT
+
+ ZV START     X(1)                     H
+ XV START     X(N-1)     1.5D+1
+
+ENDATA
+";
+        let sif = parse_sif(input).unwrap();
+        let params = sif.get_parameters();
+
+        assert_eq!(params.get("N"), Some(&3.0));
+        assert_eq!(params.get("N-1"), Some(&2.0));
+        assert_eq!(params.get("5-N"), Some(&2.0));
+        assert_eq!(params.get("2N"), Some(&6.0));
+        assert_eq!(params.get("6/N"), Some(&2.0));
+        assert_eq!(params.get("N+N"), Some(&6.0));
+        assert_eq!(params.get("RN"), Some(&3.0));
+        assert_eq!(params.get("1/H"), Some(&2.0));
+        assert_eq!(params.get("1-H"), Some(&0.5));
+        assert_eq!(params.get("3H"), Some(&1.5));
+        assert_eq!(params.get("H+1"), Some(&1.5));
+        assert_eq!(params.get("H2"), Some(&0.25));
+        assert_eq!(params.get("N/H"), Some(&6.0));
+        assert_eq!(params.get("E"), Some(&1.0));
+        assert_eq!(params.get("SQRT4"), Some(&2.0));
+        assert_eq!(params.get("ABSH"), Some(&0.5));
+        assert_eq!(params.get("N/H-INT"), Some(&6.0));
+
+        assert_eq!(sif.cols.len(), 3);
+        assert!(sif.cols.contains_key("X1"));
+        assert!(sif.cols.contains_key("X2"));
+
+        assert_eq!(sif.rows.get("C1"), Some(&RowType::G));
+        assert_eq!(sif.rows.get("C2"), Some(&RowType::L));
+        assert_eq!(
+            sif.entries.get(&("C2".to_string(), "X2".to_string())),
+            Some(&0.5)
+        );
+
+        assert_eq!(sif.rhs.get("C1"), Some(&1.5));
+        assert_eq!(sif.rhs.get("C2"), Some(&2.5));
+
+        assert_eq!(sif.bounds.get("X2"), Some(&(0.0, 4.0)));
+        assert_eq!(sif.bounds.get("X1"), Some(&(2.0, f64::INFINITY)));
+
+        assert_eq!(sif.get_start_point(), vec![0.5, 15.0, 0.0]);
+    }
+
+    #[test]
+    fn test_loops() {
+        let input = "NAME          LOOPS
+
+ IE N                   4
+ IE M                   2
+ IE 1                   1
+ IE 2                   2
+
+VARIABLES
+
+ DO I         1                        N
+ X  X(I)
+ ND
+
+ DO I         1                        M
+ DO J         1                        M
+ X  Y(I,J)
+ OD J
+ OD I
+
+GROUPS
+
+ DO I         1                        N
+ XN OBJ       X(I)       1.0
+ OD I
+
+ DO I         1                        N
+ DI I         2
+ IA I+1       I          1
+ XG C(I)      X(I)       1.0           X(I+1)     -1.0
+ ND
+
+ DO I         1                        M
+ DO J         1                        M
+ RI RI        I
+ RI RJ        J
+ R* RIJ       RI                       RJ
+ ZE D(I,J)    Y(I,J)                   RIJ
+ ND
+
+CONSTANTS
+
+ DO I         M                        1
+ DI I         -1
+ X  RHS       D(I,I)     1.0
+ ND
+
+ENDATA
+";
+        let sif = parse_sif(input).unwrap();
+
+        assert_eq!(sif.cols.len(), 8);
+        assert!(sif.cols.contains_key("X4"));
+        assert!(sif.cols.contains_key("Y1,2"));
+        assert!(sif.cols.contains_key("Y2,2"));
+
+        assert_eq!(sif.rows.len(), 7);
+        assert_eq!(sif.rows.get("C1"), Some(&RowType::G));
+        assert_eq!(sif.rows.get("C3"), Some(&RowType::G));
+        assert_eq!(sif.rows.get("C2"), None);
+        assert_eq!(sif.rows.get("D2,1"), Some(&RowType::E));
+
+        assert_eq!(
+            sif.entries
+                .keys()
+                .filter(|(row_name, _)| row_name == "OBJ")
+                .count(),
+            4
+        );
+        assert_eq!(
+            sif.entries.get(&("C3".to_string(), "X4".to_string())),
+            Some(&-1.0)
+        );
+        assert_eq!(
+            sif.entries.get(&("D2,2".to_string(), "Y2,2".to_string())),
+            Some(&4.0)
+        );
+        assert_eq!(
+            sif.entries.get(&("D1,2".to_string(), "Y1,2".to_string())),
+            Some(&2.0)
+        );
+
+        assert_eq!(sif.rhs.len(), 2);
+        assert_eq!(sif.rhs.get("D1,1"), Some(&1.0));
+        assert_eq!(sif.rhs.get("D2,2"), Some(&1.0));
+    }
+
+    #[test]
+    fn test_parameter_options() {
+        let input = "NAME          SCALABLE
+
+*   Number of variables
+
+*IE N                   10             $-PARAMETER
+ IE N                   4              $-PARAMETER     original value
+*IE N                   1000           $-PARAMETER
+
+*   Upper bound
+
+ RE UB                  2.5            $-PARAMETER
+
+VARIABLES
+
+ DO I         1                        N
+ X  X(I)
+ ND
+
+GROUPS
+
+ N  OBJ
+
+BOUNDS
+
+ DO I         1                        N
+ ZU BND       X(I)                     UB
+ ND
+
+ENDATA
+";
+        let sif = parse_sif(input).unwrap();
+        assert_eq!(sif.cols.len(), 4);
+        assert_eq!(
+            sif.get_parameter_choices().get("N"),
+            Some(&vec![10.0, 4.0, 1000.0])
+        );
+        assert_eq!(sif.get_parameter_choices().get("UB"), Some(&vec![2.5]));
+
+        let options = ParseOptions::default()
+            .with_parameter("N", 1000.0)
+            .with_parameter("UB", 7.0);
+        let sif = parse_sif_with(input, &options).unwrap();
+        assert_eq!(sif.cols.len(), 1000);
+        assert_eq!(sif.get_parameters().get("N"), Some(&1000.0));
+        assert_eq!(sif.get_column_bounds("X1000"), Some((0.0, 7.0)));
+
+        let options = ParseOptions::default().with_parameter("M", 5.0);
+        assert!(parse_sif_with(input, &options).is_err());
+    }
+
+    #[test]
+    fn test_element_types() {
+        let input = "NAME          ELTYPES
+
+VARIABLES
+
+    X
+    Y
+
+GROUPS
+
+ N  OBJ
+
+ELEMENT TYPE
+
+ EV SQ        V
+ EV 3PROD     V1                       V2
+ EV 3PROD     V3
+ IV 3PROD     U1                       U2
+ EV SINE      V
+ EP SINE      A                        B
+
+ENDATA
+";
+        let sif = parse_sif(input).unwrap();
+        let element_types = sif.get_element_types();
+
+        assert_eq!(element_types.len(), 3);
+
+        let product = &element_types["3PROD"];
+        assert_eq!(product.get_name(), "3PROD");
+        assert_eq!(product.get_elemental_variables(), &vec!["V1", "V2", "V3"]);
+        assert_eq!(product.get_internal_variables(), &vec!["U1", "U2"]);
+        assert!(product.get_parameters().is_empty());
+
+        let sine = &element_types["SINE"];
+        assert_eq!(sine.get_elemental_variables(), &vec!["V"]);
+        assert_eq!(sine.get_parameters(), &vec!["A", "B"]);
+
+        let duplicate = input.replace(" EV 3PROD     V3", " EV 3PROD     V1");
+        assert!(parse_sif(&duplicate).is_err());
+    }
+
+    #[test]
+    fn test_element_uses() {
+        let input = "NAME          ELUSES
+
+ IE N                   3
+ RE P                   0.5
+
+VARIABLES
+
+ DO I         1                        N
+ X  X(I)
+ ND
+
+GROUPS
+
+ N  OBJ
+
+ELEMENT TYPE
+
+ EV SQ        V
+ EV PROD      V1                       V2
+ EP PROD      A                        B
+
+ELEMENT USES
+
+ XT 'DEFAULT' SQ
+
+ DO I         1                        N
+ XV E(I)      V                        X(I)
+ ND
+
+ T  PR        PROD
+ V  PR        V1                       X1
+ ZV PR        V2                       X(N)
+ P  PR        A          2.0
+ ZP PR        B                        P
+
+ENDATA
+";
+        let sif = parse_sif(input).unwrap();
+        let elements = sif.get_element_uses();
+
+        assert_eq!(elements.len(), 4);
+
+        let square = &elements["E2"];
+        assert_eq!(square.get_name(), "E2");
+        assert_eq!(square.get_element_type(), "SQ");
+        assert_eq!(square.get_variables().get("V"), Some(&"X2".to_string()));
+
+        let product = &elements["PR"];
+        assert_eq!(product.get_element_type(), "PROD");
+        assert_eq!(product.get_variables().get("V1"), Some(&"X1".to_string()));
+        assert_eq!(product.get_variables().get("V2"), Some(&"X3".to_string()));
+        assert_eq!(product.get_parameters().get("A"), Some(&2.0));
+        assert_eq!(product.get_parameters().get("B"), Some(&0.5));
+
+        let undefined_column = input.replace(
+            " V  PR        V1                       X1",
+            " V  PR        V1                       Y1",
+        );
+        assert!(parse_sif(&undefined_column).is_err());
+
+        let undefined_type = input.replace(" T  PR        PROD", " T  PR        CUBE");
+        assert!(parse_sif(&undefined_type).is_err());
+
+        let unassigned = input.replace(" ZP PR        B                        P\n", "");
+        assert!(parse_sif(&unassigned).is_err());
+    }
+
+    #[test]
+    fn test_group_uses() {
+        let input = "NAME          GRUSES
+
+ IE N                   2
+ RE W                   3.0
+
+VARIABLES
+
+ DO I         1                        N
+ X  X(I)
+ ND
+
+GROUPS
+
+ N  OBJ       X1         1.0
+ XE C(1)      X1         1.0           X2         1.0
+ XE C(2)      X2         1.0
+
+ELEMENT TYPE
+
+ EV SQ        V
+
+ELEMENT USES
+
+ XT 'DEFAULT' SQ
+
+ DO I         1                        N
+ XV E(I)      V                        X(I)
+ ND
+
+GROUP TYPE
+
+ GV L2        ALPHA
+ GV POWER     ALPHA
+ GP POWER     P
+
+GROUP USES
+
+ XT 'DEFAULT' L2
+
+ T  OBJ       POWER
+ E  OBJ       E1         1.0           E2         2.0
+ P  OBJ       P          3.0
+ ZE C(1)      E2                       W
+
+ENDATA
+";
+        let sif = parse_sif(input).unwrap();
+
+        let group_types = sif.get_group_types();
+        assert_eq!(group_types.len(), 2);
+        assert_eq!(group_types["POWER"].get_group_variable(), "ALPHA");
+        assert_eq!(group_types["POWER"].get_parameters(), &vec!["P"]);
+
+        let objective = sif.get_group_use("OBJ").unwrap();
+        assert_eq!(objective.get_group_type(), "POWER");
+        assert_eq!(
+            objective.get_elements(),
+            &vec![("E1".to_string(), 1.0), ("E2".to_string(), 2.0)]
+        );
+        assert_eq!(objective.get_parameters().get("P"), Some(&3.0));
+
+        let constraint = sif.get_group_use("C1").unwrap();
+        assert_eq!(constraint.get_group_type(), "L2");
+        assert_eq!(constraint.get_elements(), &vec![("E2".to_string(), 3.0)]);
+
+        // The default group type also applies to groups without elements.
+        assert_eq!(sif.get_group_uses().len(), 3);
+        assert_eq!(sif.get_group_use("C2").unwrap().get_group_type(), "L2");
+
+        // Fields are read by position, however they are spaced.
+        let free = input.replace(
+            " E  OBJ       E1         1.0           E2         2.0",
+            " E  OBJ  E1  1.5  E2  2.0",
+        );
+        assert_eq!(
+            parse_sif(&free)
+                .unwrap()
+                .get_group_use("OBJ")
+                .unwrap()
+                .get_elements(),
+            &vec![("E1".to_string(), 1.5), ("E2".to_string(), 2.0)]
+        );
+
+        let undefined_element =
+            input.replace("           E2         2.0", "           E3         2.0");
+        assert!(parse_sif(&undefined_element).is_err());
+
+        let unassigned = input.replace(" P  OBJ       P          3.0\n", "");
+        assert!(parse_sif(&unassigned).is_err());
+    }
+
+    #[test]
+    fn test_element_functions() {
+        let input = "NAME          ELFUNS
+
+VARIABLES
+
+    X
+    Y
+
+GROUPS
+
+ N  OBJ
+
+ELEMENT TYPE
+
+ EV DIFSQ     V1                       V2
+ IV DIFSQ     U
+ EV SINE      V
+ EP SINE      A
+ EV PROD      V1                       V2
+
+ELEMENT USES
+
+ T  E1        DIFSQ
+ V  E1        V1                       X
+ V  E1        V2                       Y
+
+ENDATA
+
+ELEMENTS      ELFUNS
+
+ TEMPORARIES
+
+ R  S
+ R  HALF
+ M  SIN
+ M  COS
+
+ GLOBALS
+
+ A  HALF                0.5D0
+
+ INDIVIDUALS
+
+* Squared difference through a range transformation
+
+ T  DIFSQ
+ R  U         V1         1.0           V2         -1.0
+ F                      U * U
+ G  U                   2.0 * U
+ H  U         U         2.0
+
+ T  SINE
+ A  S                   A * SIN(V)
+ F                      S
+ G  V                   A * COS(V)
+ H  V         V         - S
+
+ T  PROD
+ F                      V1 * V2 * HALF
+ F+                     + 1 / 2
+ G  V1                  HALF * V2
+ G  V2                  HALF * V1
+ H  V1        V2        HALF
+
+ENDATA
+";
+        let sif = parse_sif(input).unwrap();
+        let functions = sif.get_element_functions();
+        assert_eq!(functions.len(), 3);
+
+        let (f, g, h) = functions["DIFSQ"].evaluate(&[3.0, 1.0], &[]).unwrap();
+        assert!(functions["DIFSQ"].evaluate(&[3.0], &[]).is_err());
+        assert_eq!(f, 4.0);
+        assert_eq!(g, vec![4.0, -4.0]);
+        assert_eq!(h, vec![vec![2.0, -2.0], vec![-2.0, 2.0]]);
+        assert_eq!(
+            functions["DIFSQ"].get_transformation(),
+            &vec![vec![1.0, -1.0]]
+        );
+
+        let x = std::f64::consts::FRAC_PI_2;
+        let (f, g, h) = functions["SINE"].evaluate(&[x], &[2.0]).unwrap();
+        assert_eq!(f, 2.0);
+        assert!(g[0].abs() < 1e-12);
+        assert_eq!(h, vec![vec![-2.0]]);
+
+        // Names and intrinsics are case-insensitive.
+        let lower_case = input
+            .replace(
+                " A  S                   A * SIN(V)",
+                " A  s                   a * sin(v)",
+            )
+            .replace(
+                " G  V                   A * COS(V)",
+                " G  v                   a * Cos(V)",
+            );
+        let sif = parse_sif(&lower_case).unwrap();
+        let (f, _, h) = sif.get_element_functions()["SINE"]
+            .evaluate(&[x], &[2.0])
+            .unwrap();
+        assert_eq!(f, 2.0);
+        assert_eq!(h, vec![vec![-2.0]]);
+
+        // 1 / 2 is an integer division and vanishes.
+        let (f, g, h) = functions["PROD"].evaluate(&[2.0, 3.0], &[]).unwrap();
+        assert_eq!(f, 3.0);
+        assert_eq!(g, vec![1.5, 1.0]);
+        assert_eq!(h, vec![vec![0.0, 0.5], vec![0.5, 0.0]]);
+
+        let undefined_name = input.replace("A * COS(V)", "B * COS(V)");
+        assert!(parse_sif(&undefined_name).is_err());
+
+        let conditional = input.replace(" A  S       ", " I  S       ");
+        assert!(parse_sif(&conditional).is_err());
+
+        let undefined_type = input.replace(" T  PROD", " T  CUBE");
+        assert!(parse_sif(&undefined_type).is_err());
+    }
+
+    #[test]
+    fn test_group_functions() {
+        let input = "NAME          GRFUNS
+
+VARIABLES
+
+    X
+
+GROUPS
+
+ N  OBJ       X          1.0
+
+GROUP TYPE
+
+ GV L2        ALPHA
+ GV POWER     T
+ GP POWER     P
+
+GROUP USES
+
+ T  OBJ       POWER
+ P  OBJ       P          3.0
+
+ENDATA
+
+GROUPS        GRFUNS
+
+ TEMPORARIES
+
+ R  PM1
+
+ INDIVIDUALS
+
+ T  L2
+ F                      ALPHA * ALPHA
+ G                      ALPHA + ALPHA
+ H                      2.0
+
+ T  POWER
+ A  PM1                 P - 1.0
+ F                      T ** P
+ G                      P * T ** PM1
+ H                      P * PM1 * T ** (P - 2.0)
+
+ENDATA
+";
+        let sif = parse_sif(input).unwrap();
+        let functions = sif.get_group_functions();
+        assert_eq!(functions.len(), 2);
+
+        assert_eq!(functions["L2"].evaluate(3.0, &[]).unwrap(), (9.0, 6.0, 2.0));
+        assert_eq!(
+            functions["POWER"].evaluate(2.0, &[3.0]).unwrap(),
+            (8.0, 12.0, 12.0)
+        );
+
+        let undefined_name = input.replace("P * T ** PM1", "Q * T ** PM1");
+        assert!(parse_sif(&undefined_name).is_err());
+    }
+
+    #[test]
+    fn test_evaluator_qp() {
+        let sif = parse_file("examples/qptest.sif").unwrap();
+        let evaluator = evaluator::Evaluator::new(&sif).unwrap();
+        let x = [1.0, 2.0];
+
+        assert_eq!(evaluator.get_num_variables(), 2);
+        assert_eq!(evaluator.get_num_constraints(), 2);
+        assert_eq!(evaluator.obj(&x).unwrap(), 25.5);
+        assert_eq!(evaluator.grad(&x).unwrap(), vec![13.5, 20.0]);
+        assert_eq!(evaluator.cons(&x).unwrap(), vec![2.0, -3.0]);
+        assert_eq!(
+            evaluator.get_constraint_bounds(),
+            &vec![(0.0, f64::INFINITY), (f64::NEG_INFINITY, 0.0)]
+        );
+        assert_eq!(
+            evaluator.get_jacobian_structure(),
+            &vec![(0, 0), (0, 1), (1, 0), (1, 1)]
+        );
+        assert_eq!(evaluator.jac(&x).unwrap(), vec![2.0, 1.0, -1.0, 2.0]);
+        assert!(evaluator.obj(&[1.0]).is_err());
+        assert!(evaluator.hess_lagrangian(&x, &[1.0]).is_err());
+        assert_eq!(
+            evaluator.get_hessian_structure(),
+            &vec![(0, 0), (1, 0), (1, 1)]
+        );
+        assert_eq!(
+            evaluator.hess_lagrangian(&x, &[1.0, 1.0]).unwrap(),
+            vec![8.0, 2.0, 10.0]
+        );
+    }
+
+    #[test]
+    fn test_evaluator_nonlinear() {
+        let input = "NAME          NLP
+
+VARIABLES
+
+    X
+    Y
+
+GROUPS
+
+ N  OBJ       Y          1.0
+ E  C1        X          1.0           'SCALE'    2.0
+
+CONSTANTS
+
+    RHS       OBJ        1.0           C1         2.0
+
+ELEMENT TYPE
+
+ EV SQ        V
+ EV PROD      V1                       V2
+
+ELEMENT USES
+
+ T  E1        SQ
+ V  E1        V                        X
+ T  E2        PROD
+ V  E2        V1                       X
+ V  E2        V2                       Y
+
+GROUP TYPE
+
+ GV L2        ALPHA
+
+GROUP USES
+
+ T  OBJ       L2
+ E  OBJ       E1
+ E  C1        E2         3.0
+
+ENDATA
+
+ELEMENTS      NLP
+
+ INDIVIDUALS
+
+ T  SQ
+ F                      V * V
+ G  V                   V + V
+ H  V         V         2.0
+
+ T  PROD
+ F                      V1 * V2
+ G  V1                  V2
+ G  V2                  V1
+ H  V1        V2        1.0
+
+ENDATA
+
+GROUPS        NLP
+
+ INDIVIDUALS
+
+ T  L2
+ F                      ALPHA * ALPHA
+ G                      ALPHA + ALPHA
+ H                      2.0
+
+ENDATA
+";
+        let sif = parse_sif(input).unwrap();
+        let evaluator = evaluator::Evaluator::new(&sif).unwrap();
+        let x = [2.0, 1.0];
+
+        // f = (y + x² - 1)², c = (x + 3xy - 2) / 2
+        assert_eq!(evaluator.obj(&x).unwrap(), 16.0);
+        assert_eq!(evaluator.grad(&x).unwrap(), vec![32.0, 8.0]);
+        assert_eq!(evaluator.cons(&x).unwrap(), vec![3.0]);
+        assert_eq!(evaluator.get_constraint_bounds(), &vec![(0.0, 0.0)]);
+        assert_eq!(evaluator.jac(&x).unwrap(), vec![2.0, 3.0]);
+        assert_eq!(
+            evaluator.get_hessian_structure(),
+            &vec![(0, 0), (1, 0), (1, 1)]
+        );
+        assert_eq!(
+            evaluator.hess_lagrangian(&x, &[2.0]).unwrap(),
+            vec![48.0, 11.0, 2.0]
+        );
+
+        // Without the function sections the element functions are unknown.
+        let data = &input[..input.find("ENDATA").unwrap() + 7];
+        let sif = parse_sif(data).unwrap();
+        assert!(evaluator::Evaluator::new(&sif).is_err());
+    }
+
+    #[test]
+    fn test_objective_sense() {
+        let input = "NAME          SENSETEST
+OBJSENSE MAX
+OBJNAME
+    profit
+ROWS
+ N  cost
+ N  profit
+ L  cap
+COLUMNS
+    x1        profit             3.0   cap                1.0
+    x2        profit             2.0   cap                1.0
+    x2        cost               1.0
+RHS
+    rhs       cap                4.0
+ENDATA
+";
+        let sif = parse_sif(input).unwrap();
+        assert_eq!(sif.get_objective_sense(), ObjSense::Max);
+        assert_eq!(sif.get_objective_name(), Some("profit"));
+
+        let section = input.replace("OBJSENSE MAX\n", "OBJSENSE\n    MAXIMIZE\n");
+        let sif = parse_sif(&section).unwrap();
+        assert_eq!(sif.get_objective_sense(), ObjSense::Max);
+
+        let spaced = input.replace("OBJSENSE MAX\n", "OBJSENSE    MAX\n");
+        let sif = parse_sif(&spaced).unwrap();
+        assert_eq!(sif.get_objective_sense(), ObjSense::Max);
+
+        let sif = parse_sif(&input.replace("OBJSENSE MAX\n", "")).unwrap();
+        assert_eq!(sif.get_objective_sense(), ObjSense::Min);
+
+        let qp = parse_file("examples/qptest.sif").unwrap();
+        let maximized = parse_sif(
+            &std::fs::read_to_string("examples/qptest.sif")
+                .unwrap()
+                .replacen("ROWS", "OBJSENSE\n    MAX\nROWS", 1),
+        )
+        .unwrap();
+        let x = [1.0, 2.0];
+        let min = evaluator::Evaluator::new(&qp).unwrap();
+        let max = evaluator::Evaluator::new(&maximized).unwrap();
+        assert_eq!(max.obj(&x).unwrap(), -min.obj(&x).unwrap());
+        assert_eq!(max.grad(&x).unwrap(), vec![-13.5, -20.0]);
+        assert_eq!(max.cons(&x).unwrap(), min.cons(&x).unwrap());
+        assert_eq!(
+            max.hess_lagrangian(&x, &[0.0, 0.0]).unwrap(),
+            vec![-8.0, -2.0, -10.0]
+        );
+
+        // The exported data follows the same convention as the evaluator.
+        let maximized = parse_sif(
+            &std::fs::read_to_string("examples/qptest.sif")
+                .unwrap()
+                .replacen("ROWS", "OBJSENSE\n    MAX\nROWS", 1)
+                .replace(
+                    "rhs1      r1",
+                    "rhs1      obj                4.0\n    rhs1      r1",
+                ),
+        )
+        .unwrap();
+        assert_eq!(maximized.get_objective_offset(), 4.0);
+        let linear: f64 = maximized
+            .get_objective_vector()
+            .iter()
+            .zip(&x)
+            .map(|(c, x)| c * x)
+            .sum();
+        let quadratic: f64 = maximized
+            .get_quadratic_coo()
+            .iter()
+            .map(|(i, j, q)| if i == j { 0.5 } else { 1.0 } * q * x[*i] * x[*j])
+            .sum();
+        assert_eq!(
+            evaluator::Evaluator::new(&maximized)
+                .unwrap()
+                .obj(&x)
+                .unwrap(),
+            linear + quadratic + maximized.get_objective_offset()
+        );
+
+        // The objective constant is negated with the rest of the objective.
+        let offset = input.replace(
+            "    rhs       cap                4.0",
+            "    rhs       profit             1.0   cap                4.0",
+        );
+        assert_eq!(parse_sif(&offset).unwrap().get_objective_offset(), 1.0);
+
+        assert!(parse_sif(&input.replace("OBJSENSE MAX", "OBJSENSE UP")).is_err());
+        assert!(parse_sif(&input.replace("    profit\n", "    cap\n")).is_err());
+    }
+
+    #[test]
+    fn test_objective_row() {
+        let input = "NAME          FREEROWS
+ROWS
+ L  cap
+ N  cost
+ N  alt
+COLUMNS
+    x1        cost               1.0   alt                5.0
+    x1        cap                1.0
+    x2        cost               2.0   alt                1.0
+RHS
+    rhs       cost               1.0   alt                2.0
+ENDATA
+";
+        let sif = parse_sif(input).unwrap();
+        assert_eq!(sif.get_objective_name(), Some("cost"));
+        assert_eq!(sif.get_objective_offset(), -1.0);
+        assert_eq!(sif.get_rows().len(), 3);
+        assert_eq!(sif.get_rhs().len(), 0);
+
+        let options = ParseOptions::default().with_objective_row("alt");
+        let sif = parse_sif_with(input, &options).unwrap();
+        assert_eq!(sif.get_objective_name(), Some("alt"));
+        assert_eq!(sif.get_objective_offset(), -2.0);
+        let evaluator = evaluator::Evaluator::new(&sif).unwrap();
+        assert_eq!(evaluator.obj(&[1.0, 1.0]).unwrap(), 4.0);
+        assert_eq!(evaluator.get_num_constraints(), 1);
+
+        // The default objective row is evaluated alone, as if it were named.
+        let sif = parse_sif(input).unwrap();
+        let evaluator = evaluator::Evaluator::new(&sif).unwrap();
+        assert_eq!(evaluator.obj(&[1.0, 1.0]).unwrap(), 2.0);
+        let options = ParseOptions::default().with_objective_row("cost");
+        let named = evaluator::Evaluator::new(&parse_sif_with(input, &options).unwrap()).unwrap();
+        assert_eq!(named.obj(&[1.0, 1.0]).unwrap(), 2.0);
+
+        let sif = parse_sif(&input.replace("ROWS\n", "OBJNAME alt\nROWS\n")).unwrap();
+        assert_eq!(sif.get_objective_name(), Some("alt"));
+
+        let options = ParseOptions::default().with_drop_free_rows(true);
+        let sif = parse_sif_with(input, &options).unwrap();
+        assert_eq!(sif.get_objective_name(), Some("cost"));
+        assert_eq!(sif.get_rows().len(), 2);
+        assert_eq!(sif.get_rows().get("alt"), None);
+        assert!(
+            sif.get_entries()
+                .keys()
+                .all(|(row_name, _)| row_name != "alt")
+        );
+        let evaluator = evaluator::Evaluator::new(&sif).unwrap();
+        assert_eq!(evaluator.obj(&[1.0, 1.0]).unwrap(), 2.0);
+
+        let options = ParseOptions::default().with_objective_row("cap");
+        assert!(parse_sif_with(input, &options).is_err());
+    }
+
+    #[test]
+    fn test_file_order() {
+        let sif = parse_file("examples/AFIRO.SIF").unwrap();
+
+        assert_eq!(sif.get_row_names().len(), 28);
+        assert_eq!(sif.get_col_names().len(), 32);
+        assert_eq!(sif.get_row_name(0), Some("R09"));
+        assert_eq!(sif.get_row_name(2), Some("X05"));
+        assert_eq!(sif.get_row_index("R10"), Some(1));
+        assert_eq!(sif.get_col_name(0), Some("X01"));
+        assert_eq!(sif.get_col_index("X04"), Some(3));
+        assert_eq!(sif.get_row_index("NONE"), None);
+        assert_eq!(sif.get_col_name(32), None);
+
+        for (position, name) in sif.get_col_names().iter().enumerate() {
+            assert_eq!(sif.get_col_index(name), Some(position));
+        }
+
+        let evaluator = evaluator::Evaluator::new(&sif).unwrap();
+        assert_eq!(evaluator.get_variable_names(), sif.get_col_names());
+        assert_eq!(evaluator.get_constraint_names()[..3], ["R09", "R10", "X05"]);
+    }
+
+    #[test]
+    fn test_constraint_matrix() {
+        let input = "NAME          MATRIX
+ROWS
+ L  cap
+ N  cost
+ E  bal
+COLUMNS
+    x1        cost               1.0   cap                2.0
+    x1        bal                3.0
+    x2        cap                4.0
+    x3        cost              -1.0   bal                5.0
+ENDATA
+";
+        let sif = parse_sif(input).unwrap();
+
+        assert_eq!(sif.get_constraint_row_names(), vec!["cap", "bal"]);
+        assert_eq!(
+            sif.get_constraint_matrix_coo(),
+            vec![(0, 0, 2.0), (1, 0, 3.0), (0, 1, 4.0), (1, 2, 5.0)]
+        );
+
+        let csc = sif.get_constraint_matrix_csc();
+        assert_eq!((csc.get_nrows(), csc.get_ncols()), (2, 3));
+        assert_eq!(csc.get_col_ptr(), &vec![0, 2, 3, 4]);
+        assert_eq!(csc.get_row_indices(), &vec![0, 1, 0, 1]);
+        assert_eq!(csc.get_values(), &vec![2.0, 3.0, 4.0, 5.0]);
+
+        let csr = sif.get_constraint_matrix_csr();
+        assert_eq!((csr.get_nrows(), csr.get_ncols()), (2, 3));
+        assert_eq!(csr.get_row_ptr(), &vec![0, 2, 4]);
+        assert_eq!(csr.get_col_indices(), &vec![0, 1, 0, 2]);
+        assert_eq!(csr.get_values(), &vec![2.0, 4.0, 3.0, 5.0]);
+
+        assert_eq!(sif.get_objective_vector(), vec![1.0, 0.0, -1.0]);
+        let sif = parse_sif(&input.replace("ROWS\n", "OBJSENSE MAX\nROWS\n")).unwrap();
+        assert_eq!(sif.get_objective_vector(), vec![-1.0, 0.0, 1.0]);
+
+        // Further free rows are no constraints either, as in the evaluator.
+        let sif = parse_sif(&input.replace(" E  bal\n", " E  bal\n N  alt\n").replace(
+            "    x2        cap                4.0\n",
+            "    x2        cap                4.0   alt                6.0\n",
+        ))
+        .unwrap();
+        assert_eq!(sif.get_constraint_row_names(), vec!["cap", "bal"]);
+        assert_eq!(sif.get_constraint_matrix_csr().get_nrows(), 2);
+        assert_eq!(
+            sif.get_constraint_matrix_coo(),
+            vec![(0, 0, 2.0), (1, 0, 3.0), (0, 1, 4.0), (1, 2, 5.0)]
+        );
+        assert_eq!(
+            evaluator::Evaluator::new(&sif)
+                .unwrap()
+                .get_constraint_names(),
+            &vec!["cap".to_string(), "bal".to_string()]
+        );
+
+        let sif = parse_file("examples/AFIRO.SIF").unwrap();
+        let csc = sif.get_constraint_matrix_csc();
+        assert_eq!((csc.get_nrows(), csc.get_ncols()), (27, 32));
+        assert_eq!(
+            csc.get_values().len()
+                + sif
+                    .get_objective_vector()
+                    .iter()
+                    .filter(|c| **c != 0.0)
+                    .count(),
+            sif.get_entries().len()
+        );
+    }
+
+    #[test]
+    fn test_quadratic_matrix() {
+        let input = std::fs::read_to_string("examples/qptest.sif").unwrap();
+        let sif = parse_sif(&input).unwrap();
+
+        assert_eq!(
+            sif.get_quadratic_coo(),
+            vec![(0, 0, 8.0), (1, 0, 2.0), (1, 1, 10.0)]
+        );
+
+        let lower = sif.get_quadratic_csc_lower();
+        assert_eq!(lower.get_col_ptr(), &vec![0, 2, 3]);
+        assert_eq!(lower.get_row_indices(), &vec![0, 1, 1]);
+        assert_eq!(lower.get_values(), &vec![8.0, 2.0, 10.0]);
+
+        let upper = sif.get_quadratic_csc_upper();
+        assert_eq!(upper.get_col_ptr(), &vec![0, 1, 3]);
+        assert_eq!(upper.get_row_indices(), &vec![0, 0, 1]);
+        assert_eq!(upper.get_values(), &vec![8.0, 2.0, 10.0]);
+
+        let full = sif.get_quadratic_csc();
+        assert_eq!((full.get_nrows(), full.get_ncols()), (2, 2));
+        assert_eq!(full.get_col_ptr(), &vec![0, 2, 4]);
+        assert_eq!(full.get_row_indices(), &vec![0, 1, 0, 1]);
+        assert_eq!(full.get_values(), &vec![8.0, 2.0, 2.0, 10.0]);
+
+        // Both triangles, each off-diagonal entry counting for half.
+        let qmatrix = input.replace(
+            "QUADOBJ\n    c1        c1                 8.0\n",
+            "QMATRIX\n    c1        c1                 8.0\n    c2        c1                 2.0\n",
+        );
+        let sif = parse_sif(&qmatrix.replace("QMATRIX", "QUADOBJ")).unwrap();
+        assert_eq!(sif.get_quadratic_coo()[1], (1, 0, 4.0));
+        let sif = parse_sif(&qmatrix).unwrap();
+        assert_eq!(
+            sif.get_quadratic_coo(),
+            vec![(0, 0, 8.0), (1, 0, 2.0), (1, 1, 10.0)]
+        );
+
+        // Repeated pairs add up.
+        let repeated = input.replace("ENDATA", "    c2        c2                 1.0\nENDATA");
+        let sif = parse_sif(&repeated).unwrap();
+        assert_eq!(
+            sif.get_quadratic()[&("c2".to_string(), "c2".to_string())],
+            11.0
+        );
+        assert_eq!(sif.get_quadratic_coo()[2], (1, 1, 11.0));
+
+        // Two entries on one line.
+        let hessian = input.replace(
+            "QUADOBJ\n    c1        c1                 8.0\n    c1        c2                 2.0\n",
+            "HESSIAN\n    c1        c1                 8.0   c2                 2.0\n",
+        );
+        let sif = parse_sif(&hessian).unwrap();
+        assert_eq!(sif.get_quadratic().len(), 3);
+        assert_eq!(
+            sif.get_quadratic_coo(),
+            vec![(0, 0, 8.0), (1, 0, 2.0), (1, 1, 10.0)]
+        );
+
+        let sif = parse_sif(&input.replacen("ROWS", "OBJSENSE MAX\nROWS", 1)).unwrap();
+        assert_eq!(
+            sif.get_quadratic_coo(),
+            vec![(0, 0, -8.0), (1, 0, -2.0), (1, 1, -10.0)]
+        );
+    }
+
     #[test]
     fn test_exdata() {
         let input = std::fs::read_to_string("examples/EXDATA.SIF").unwrap();
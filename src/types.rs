@@ -1,4 +1,4 @@
-use std::str::FromStr;
+use std::{collections::BTreeMap, fmt, str::FromStr};
 
 use crate::ParseError;
 
@@ -25,7 +25,16 @@ pub(crate) enum Major {
 /// | `E`     | Equality constraint (=) |
 ///
 /// The prefixed variants (`X*`, `Z*`, `D*`) are LANCELOT/SIF extensions used
-/// for nonlinear group types.
+/// for nonlinear group types:
+///
+/// | Prefix | Meaning |
+/// |--------|---------|
+/// | `X`    | Names may carry indices, e.g. `C(I,J)`, expanded with the current parameter values |
+/// | `Z`    | As `X`, but the numeric value is the value of a named parameter |
+/// | `D`    | The row is a linear combination of two previously defined rows |
+///
+/// Extended rows are resolved while parsing, so the rows of a parsed [`crate::SIF`]
+/// only use the single-letter variants.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum RowType {
     /// Free row (no constraint); usually the objective function.
@@ -36,19 +45,44 @@ pub enum RowType {
     L,
     /// Equality (=) constraint.
     E,
-    // XN,
-    // XG,
-    // XL,
-    // XE,
-    // ZN,
-    // ZG,
-    // ZL,
-    // ZE,
-
-    // DN,
-    // DG,
-    // DL,
-    // DE,
+    /// Indexed free row.
+    XN,
+    /// Indexed ≥ constraint.
+    XG,
+    /// Indexed ≤ constraint.
+    XL,
+    /// Indexed equality constraint.
+    XE,
+    /// Free row with a parameter value.
+    ZN,
+    /// ≥ constraint with a parameter value.
+    ZG,
+    /// ≤ constraint with a parameter value.
+    ZL,
+    /// Equality constraint with a parameter value.
+    ZE,
+
+    /// Free row combining two other rows.
+    DN,
+    /// ≥ constraint combining two other rows.
+    DG,
+    /// ≤ constraint combining two other rows.
+    DL,
+    /// Equality constraint combining two other rows.
+    DE,
+}
+
+impl RowType {
+    /// Returns the single-letter row type underlying an extended row type,
+    /// e.g. `G` for `XG`, `ZG` and `DG`.
+    pub fn base(&self) -> RowType {
+        match self {
+            RowType::N | RowType::XN | RowType::ZN | RowType::DN => RowType::N,
+            RowType::G | RowType::XG | RowType::ZG | RowType::DG => RowType::G,
+            RowType::L | RowType::XL | RowType::ZL | RowType::DL => RowType::L,
+            RowType::E | RowType::XE | RowType::ZE | RowType::DE => RowType::E,
+        }
+    }
 }
 
 impl FromStr for RowType {
@@ -60,18 +94,18 @@ impl FromStr for RowType {
             "G" => Ok(RowType::G),
             "L" => Ok(RowType::L),
             "E" => Ok(RowType::E),
-            // "XN" => Ok(SifRowType::XN),
-            // "XG" => Ok(SifRowType::XG),
-            // "XL" => Ok(SifRowType::XL),
-            // "XE" => Ok(SifRowType::XE),
-            // "ZN" => Ok(SifRowType::ZN),
-            // "ZG" => Ok(SifRowType::ZG),
-            // "ZL" => Ok(SifRowType::ZL),
-            // "ZE" => Ok(SifRowType::ZE),
-            // "DN" => Ok(SifRowType::DN),
-            // "DG" => Ok(SifRowType::DG),
-            // "DL" => Ok(SifRowType::DL),
-            // "DE" => Ok(SifRowType::DE),
+            "XN" => Ok(RowType::XN),
+            "XG" => Ok(RowType::XG),
+            "XL" => Ok(RowType::XL),
+            "XE" => Ok(RowType::XE),
+            "ZN" => Ok(RowType::ZN),
+            "ZG" => Ok(RowType::ZG),
+            "ZL" => Ok(RowType::ZL),
+            "ZE" => Ok(RowType::ZE),
+            "DN" => Ok(RowType::DN),
+            "DG" => Ok(RowType::DG),
+            "DL" => Ok(RowType::DL),
+            "DE" => Ok(RowType::DE),
             _ => Err(ParseError {
                 message: format!("Unknown row type: {}", s.trim()),
             }),
@@ -79,26 +113,27 @@ impl FromStr for RowType {
     }
 }
 
-impl ToString for RowType {
-    fn to_string(&self) -> String {
-        match self {
-            RowType::N => "N".to_string(),
-            RowType::G => "G".to_string(),
-            RowType::L => "L".to_string(),
-            RowType::E => "E".to_string(),
-            // SifRowType::XN => "XN".to_string(),
-            // SifRowType::XG => "XG".to_string(),
-            // SifRowType::XL => "XL".to_string(),
-            // SifRowType::XE => "XE".to_string(),
-            // SifRowType::ZN => "ZN".to_string(),
-            // SifRowType::ZG => "ZG".to_string(),
-            // SifRowType::ZL => "ZL".to_string(),
-            // SifRowType::ZE => "ZE".to_string(),
-            // SifRowType::DN => "DN".to_string(),
-            // SifRowType::DG => "DG".to_string(),
-            // SifRowType::DL => "DL".to_string(),
-            // SifRowType::DE => "DE".to_string(),
-        }
+impl fmt::Display for RowType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            RowType::N => "N",
+            RowType::G => "G",
+            RowType::L => "L",
+            RowType::E => "E",
+            RowType::XN => "XN",
+            RowType::XG => "XG",
+            RowType::XL => "XL",
+            RowType::XE => "XE",
+            RowType::ZN => "ZN",
+            RowType::ZG => "ZG",
+            RowType::ZL => "ZL",
+            RowType::ZE => "ZE",
+            RowType::DN => "DN",
+            RowType::DG => "DG",
+            RowType::DL => "DL",
+            RowType::DE => "DE",
+        };
+        f.write_str(s)
     }
 }
 
@@ -112,6 +147,11 @@ impl ToString for RowType {
 /// | `__`    | ` `    | Continuous variable (default) |
 /// | `X`     | `X`    | Integer / general-integer variable |
 /// | `Z`     | `Z`    | Binary (0-1 integer) variable |
+///
+/// Columns inside an MPS `'MARKER'` block opened by `'INTORG'` and closed by
+/// `'INTEND'` are also read as integer variables. In SIF files an `X` card
+/// naming an indexed column such as `X(I)`, or a `Z` card whose value is a
+/// parameter, declares a continuous variable instead.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum ColumnType {
     /// Continuous variable (blank marker).
@@ -127,7 +167,7 @@ impl FromStr for ColumnType {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.trim() {
-            " " => Ok(ColumnType::__),
+            "" => Ok(ColumnType::__),
             "X" => Ok(ColumnType::X),
             "Z" => Ok(ColumnType::Z),
             _ => Err(ParseError {
@@ -137,13 +177,14 @@ impl FromStr for ColumnType {
     }
 }
 
-impl ToString for ColumnType {
-    fn to_string(&self) -> String {
-        match self {
-            ColumnType::__ => " ".to_string(),
-            ColumnType::X => "X".to_string(),
-            ColumnType::Z => "Z".to_string(),
-        }
+impl fmt::Display for ColumnType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            ColumnType::__ => " ",
+            ColumnType::X => "X",
+            ColumnType::Z => "Z",
+        };
+        f.write_str(s)
     }
 }
 
@@ -159,6 +200,10 @@ impl ToString for ColumnType {
 /// | `Fr`    | `FR` | Free variable (−∞ to +∞) |
 /// | `Mi`    | `MI` | Lower bound of −∞ (upper stays at default) |
 /// | `Pl`    | `PL` | Upper bound of +∞ (default upper) |
+/// | `Bv`    | `BV` | Binary variable (bounds `[0, 1]`) |
+/// | `Li`    | `LI` | Integer variable with explicit lower bound |
+/// | `Ui`    | `UI` | Integer variable with explicit upper bound |
+/// | `Sc`    | `SC` | Semi-continuous variable with upper bound |
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum BoundType {
     /// Explicit lower bound.
@@ -173,6 +218,15 @@ pub enum BoundType {
     Mi,
     /// Upper bound of +∞ (the default); lower bound unchanged.
     Pl,
+    /// Binary variable: integer with bounds `[0, 1]`.
+    Bv,
+    /// Explicit lower bound on an integer variable.
+    Li,
+    /// Explicit upper bound on an integer variable.
+    Ui,
+    /// Semi-continuous variable: either zero or within its bounds, with the
+    /// given upper bound (`0` meaning +∞).
+    Sc,
 }
 
 impl FromStr for BoundType {
@@ -186,6 +240,10 @@ impl FromStr for BoundType {
             "FR" => Ok(BoundType::Fr),
             "MI" => Ok(BoundType::Mi),
             "PL" => Ok(BoundType::Pl),
+            "BV" => Ok(BoundType::Bv),
+            "LI" => Ok(BoundType::Li),
+            "UI" => Ok(BoundType::Ui),
+            "SC" => Ok(BoundType::Sc),
             _ => Err(ParseError {
                 message: format!("Unknown bound type: {}", s.trim()),
             }),
@@ -193,17 +251,343 @@ impl FromStr for BoundType {
     }
 }
 
-impl ToString for BoundType {
-    fn to_string(&self) -> String {
-        match self {
-            BoundType::Lo => "LO".to_string(),
-            BoundType::Up => "UP".to_string(),
-            BoundType::Fx => "FX".to_string(),
-            BoundType::Fr => "FR".to_string(),
-            BoundType::Mi => "MI".to_string(),
-            BoundType::Pl => "PL".to_string(),
+impl fmt::Display for BoundType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            BoundType::Lo => "LO",
+            BoundType::Up => "UP",
+            BoundType::Fx => "FX",
+            BoundType::Fr => "FR",
+            BoundType::Mi => "MI",
+            BoundType::Pl => "PL",
+            BoundType::Bv => "BV",
+            BoundType::Li => "LI",
+            BoundType::Ui => "UI",
+            BoundType::Sc => "SC",
+        };
+        f.write_str(s)
+    }
+}
+
+/// The optimization direction given by an `OBJSENSE` card.
+///
+/// Problems without the card are minimized. Besides `MIN` and `MAX`, the
+/// spelled-out `MINIMIZE`/`MAXIMIZE` forms written by some MPS tools are
+/// accepted.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum ObjSense {
+    /// Minimize the objective (the default).
+    #[default]
+    Min,
+    /// Maximize the objective.
+    Max,
+}
+
+impl FromStr for ObjSense {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "MIN" | "MINIMIZE" | "MINIMISE" => Ok(ObjSense::Min),
+            "MAX" | "MAXIMIZE" | "MAXIMISE" => Ok(ObjSense::Max),
+            _ => Err(ParseError {
+                message: format!("Unknown objective sense: {}", s.trim()),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for ObjSense {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            ObjSense::Min => "MIN",
+            ObjSense::Max => "MAX",
+        };
+        f.write_str(s)
+    }
+}
+
+/// A nonlinear element type declared in the `ELEMENT TYPE` section.
+///
+/// Each type lists its elemental variables (`EV` cards), the internal
+/// variables the element function is written in when a range transformation
+/// is used (`IV` cards), and its parameters (`EP` cards), all in file order.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ElementType {
+    pub(crate) name: String,
+    pub(crate) elemental_variables: Vec<String>,
+    pub(crate) internal_variables: Vec<String>,
+    pub(crate) parameters: Vec<String>,
+}
+
+impl ElementType {
+    /// Returns the name of the element type.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the names of the elemental variables.
+    pub fn get_elemental_variables(&self) -> &Vec<String> {
+        &self.elemental_variables
+    }
+
+    /// Returns the names of the internal variables; empty when the element
+    /// has no range transformation.
+    pub fn get_internal_variables(&self) -> &Vec<String> {
+        &self.internal_variables
+    }
+
+    /// Returns the names of the element parameters.
+    pub fn get_parameters(&self) -> &Vec<String> {
+        &self.parameters
+    }
+}
+
+/// A nonlinear element declared in the `ELEMENT USES` section.
+///
+/// An element instantiates an [`ElementType`]: each elemental variable is
+/// bound to a problem column (`V` cards) and each element parameter is given
+/// a value (`P` cards).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ElementUse {
+    pub(crate) name: String,
+    pub(crate) element_type: String,
+    pub(crate) variables: BTreeMap<String, String>,
+    pub(crate) parameters: BTreeMap<String, f64>,
+}
+
+impl ElementUse {
+    /// Returns the name of the element.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the name of the element type, either assigned with a `T` card
+    /// or the `'DEFAULT'` type.
+    pub fn get_element_type(&self) -> &str {
+        &self.element_type
+    }
+
+    /// Returns the problem column bound to each elemental variable.
+    pub fn get_variables(&self) -> &BTreeMap<String, String> {
+        &self.variables
+    }
+
+    /// Returns the value of each element parameter.
+    pub fn get_parameters(&self) -> &BTreeMap<String, f64> {
+        &self.parameters
+    }
+}
+
+/// A nonlinear group type declared in the `GROUP TYPE` section.
+///
+/// A group type names the variable its group function is written in (`GV`
+/// card) and its parameters (`GP` cards).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GroupType {
+    pub(crate) name: String,
+    pub(crate) group_variable: String,
+    pub(crate) parameters: Vec<String>,
+}
+
+impl GroupType {
+    /// Returns the name of the group type.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the name of the group variable.
+    pub fn get_group_variable(&self) -> &str {
+        &self.group_variable
+    }
+
+    /// Returns the names of the group parameters.
+    pub fn get_parameters(&self) -> &Vec<String> {
+        &self.parameters
+    }
+}
+
+/// The nonlinear part of a group (row), declared in the `GROUP USES` section.
+///
+/// The group function of the [`GroupType`] is applied to the linear part of
+/// the row plus the weighted sum of its nonlinear elements. A group without a
+/// type is trivial: the group function is the identity.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GroupUse {
+    pub(crate) name: String,
+    pub(crate) group_type: String,
+    pub(crate) elements: Vec<(String, f64)>,
+    pub(crate) parameters: BTreeMap<String, f64>,
+}
+
+impl GroupUse {
+    /// Returns the name of the group, which is also the name of its row.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the name of the group type, or an empty string for a trivial
+    /// group.
+    pub fn get_group_type(&self) -> &str {
+        &self.group_type
+    }
+
+    /// Returns the nonlinear elements of the group with their weights, in
+    /// file order.
+    pub fn get_elements(&self) -> &Vec<(String, f64)> {
+        &self.elements
+    }
+
+    /// Returns the value of each group parameter.
+    pub fn get_parameters(&self) -> &BTreeMap<String, f64> {
+        &self.parameters
+    }
+}
+
+/// A sparse matrix in compressed sparse column (CSC) form.
+///
+/// The row indices and values of column `j` are stored at positions
+/// `col_ptr[j]..col_ptr[j + 1]`, sorted by row.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CscMatrix {
+    pub(crate) nrows: usize,
+    pub(crate) ncols: usize,
+    pub(crate) col_ptr: Vec<usize>,
+    pub(crate) row_indices: Vec<usize>,
+    pub(crate) values: Vec<f64>,
+}
+
+impl CscMatrix {
+    /// Builds the matrix from `(row, col, value)` triplets with distinct
+    /// positions.
+    pub(crate) fn from_triplets(
+        nrows: usize,
+        ncols: usize,
+        triplets: &[(usize, usize, f64)],
+    ) -> Self {
+        let (col_ptr, row_indices, values) = compress(
+            ncols,
+            triplets
+                .iter()
+                .map(|(row, col, value)| (*col, *row, *value)),
+        );
+        CscMatrix {
+            nrows,
+            ncols,
+            col_ptr,
+            row_indices,
+            values,
         }
     }
+
+    /// Returns the number of rows.
+    pub fn get_nrows(&self) -> usize {
+        self.nrows
+    }
+
+    /// Returns the number of columns.
+    pub fn get_ncols(&self) -> usize {
+        self.ncols
+    }
+
+    /// Returns the `ncols + 1` offsets of the columns into the row indices
+    /// and values.
+    pub fn get_col_ptr(&self) -> &Vec<usize> {
+        &self.col_ptr
+    }
+
+    /// Returns the row index of every stored value.
+    pub fn get_row_indices(&self) -> &Vec<usize> {
+        &self.row_indices
+    }
+
+    /// Returns the stored values, column by column.
+    pub fn get_values(&self) -> &Vec<f64> {
+        &self.values
+    }
+}
+
+/// A sparse matrix in compressed sparse row (CSR) form.
+///
+/// The column indices and values of row `i` are stored at positions
+/// `row_ptr[i]..row_ptr[i + 1]`, sorted by column.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CsrMatrix {
+    pub(crate) nrows: usize,
+    pub(crate) ncols: usize,
+    pub(crate) row_ptr: Vec<usize>,
+    pub(crate) col_indices: Vec<usize>,
+    pub(crate) values: Vec<f64>,
+}
+
+impl CsrMatrix {
+    /// Builds the matrix from `(row, col, value)` triplets with distinct
+    /// positions.
+    pub(crate) fn from_triplets(
+        nrows: usize,
+        ncols: usize,
+        triplets: &[(usize, usize, f64)],
+    ) -> Self {
+        let (row_ptr, col_indices, values) = compress(nrows, triplets.iter().copied());
+        CsrMatrix {
+            nrows,
+            ncols,
+            row_ptr,
+            col_indices,
+            values,
+        }
+    }
+
+    /// Returns the number of rows.
+    pub fn get_nrows(&self) -> usize {
+        self.nrows
+    }
+
+    /// Returns the number of columns.
+    pub fn get_ncols(&self) -> usize {
+        self.ncols
+    }
+
+    /// Returns the `nrows + 1` offsets of the rows into the column indices
+    /// and values.
+    pub fn get_row_ptr(&self) -> &Vec<usize> {
+        &self.row_ptr
+    }
+
+    /// Returns the column index of every stored value.
+    pub fn get_col_indices(&self) -> &Vec<usize> {
+        &self.col_indices
+    }
+
+    /// Returns the stored values, row by row.
+    pub fn get_values(&self) -> &Vec<f64> {
+        &self.values
+    }
+}
+
+/// Compresses `(outer, inner, value)` triplets into the offsets of each of the
+/// `n_outer` outer indices, followed by the inner indices and values sorted
+/// by outer and then inner index.
+fn compress(
+    n_outer: usize,
+    triplets: impl Iterator<Item = (usize, usize, f64)>,
+) -> (Vec<usize>, Vec<usize>, Vec<f64>) {
+    let mut triplets: Vec<(usize, usize, f64)> = triplets.collect();
+    triplets.sort_by_key(|(outer, inner, _)| (*outer, *inner));
+
+    let mut ptr = vec![0; n_outer + 1];
+    for (outer, _, _) in &triplets {
+        ptr[outer + 1] += 1;
+    }
+    for k in 0..n_outer {
+        ptr[k + 1] += ptr[k];
+    }
+
+    let (indices, values) = triplets
+        .iter()
+        .map(|(_, inner, value)| (*inner, *value))
+        .unzip();
+    (ptr, indices, values)
 }
 
 /// A SIF section indicator (the all-caps keyword that begins each section).
@@ -249,6 +633,8 @@ pub enum Indicator {
     QuadObjective,
     /// `QSECTION` — quadratic section header (alternative QPS notation).
     QSection,
+    /// `QMATRIX` — quadratic objective with both triangles given (CPLEX).
+    QMatrix,
     /// `ELEMENT TYPE` — nonlinear element-type definitions (LANCELOT).
     ElementType,
     /// `ELEMENT USES` — nonlinear element instantiations (LANCELOT).
@@ -259,38 +645,50 @@ pub enum Indicator {
     GroupUses,
     /// `OBJECT BOUNDS` — known bounds on the objective value.
     ObjectBounds,
+    /// `OBJSENSE` — objective sense, `MIN` or `MAX` (free MPS).
+    ObjSense,
+    /// `OBJNAME` — name of the objective row (free MPS).
+    ObjName,
+
+    /// `ELEMENTS` — element function definitions, after the first `ENDATA`.
+    Elements,
 
     /// `ENDATA` — end-of-file marker; must be the last indicator.
     Endata,
 }
 
-impl ToString for Indicator {
-    fn to_string(&self) -> String {
-        match self {
-            Indicator::Name => "NAME".to_string(),
-            Indicator::Groups => "GROUPS".to_string(),
-            Indicator::Rows => "ROWS".to_string(),
-            Indicator::Constraints => "CONSTRAINTS".to_string(),
-            Indicator::Variables => "VARIABLES".to_string(),
-            Indicator::Columns => "COLUMNS".to_string(),
-            Indicator::Constants => "CONSTANTS".to_string(),
-            Indicator::Rhs => "RHS".to_string(),
-            Indicator::RhsPrime => "RHS'".to_string(),
-            Indicator::Ranges => "RANGES".to_string(),
-            Indicator::Bounds => "BOUNDS".to_string(),
-            Indicator::StartPoint => "START POINT".to_string(),
-            Indicator::Quadratic => "QUADRATIC".to_string(),
-            Indicator::Hessian => "HESSIAN".to_string(),
-            Indicator::Quads => "QUADS".to_string(),
-            Indicator::QuadObjective => "QUADOBJ".to_string(),
-            Indicator::QSection => "QSECTION".to_string(),
-            Indicator::ElementType => "ELEMENT TYPE".to_string(),
-            Indicator::ElementUses => "ELEMENT USES".to_string(),
-            Indicator::GroupType => "GROUP TYPE".to_string(),
-            Indicator::GroupUses => "GROUP USES".to_string(),
-            Indicator::ObjectBounds => "OBJECT BOUNDS".to_string(),
-            Indicator::Endata => "ENDATA".to_string(),
-        }
+impl fmt::Display for Indicator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Indicator::Name => "NAME",
+            Indicator::Groups => "GROUPS",
+            Indicator::Rows => "ROWS",
+            Indicator::Constraints => "CONSTRAINTS",
+            Indicator::Variables => "VARIABLES",
+            Indicator::Columns => "COLUMNS",
+            Indicator::Constants => "CONSTANTS",
+            Indicator::Rhs => "RHS",
+            Indicator::RhsPrime => "RHS'",
+            Indicator::Ranges => "RANGES",
+            Indicator::Bounds => "BOUNDS",
+            Indicator::StartPoint => "START POINT",
+            Indicator::Quadratic => "QUADRATIC",
+            Indicator::Hessian => "HESSIAN",
+            Indicator::Quads => "QUADS",
+            Indicator::QuadObjective => "QUADOBJ",
+            Indicator::QSection => "QSECTION",
+            Indicator::QMatrix => "QMATRIX",
+            Indicator::ElementType => "ELEMENT TYPE",
+            Indicator::ElementUses => "ELEMENT USES",
+            Indicator::GroupType => "GROUP TYPE",
+            Indicator::GroupUses => "GROUP USES",
+            Indicator::ObjectBounds => "OBJECT BOUNDS",
+            Indicator::ObjSense => "OBJSENSE",
+            Indicator::ObjName => "OBJNAME",
+            Indicator::Elements => "ELEMENTS",
+            Indicator::Endata => "ENDATA",
+        };
+        f.write_str(s)
     }
 }
 
@@ -316,11 +714,15 @@ impl FromStr for Indicator {
             "QUADS" => Ok(Indicator::Quads),
             "QUADOBJ" => Ok(Indicator::QuadObjective),
             "QSECTION" => Ok(Indicator::QSection),
+            "QMATRIX" => Ok(Indicator::QMatrix),
             "ELEMENT TYPE" => Ok(Indicator::ElementType),
             "ELEMENT USES" => Ok(Indicator::ElementUses),
             "GROUP TYPE" => Ok(Indicator::GroupType),
             "GROUP USES" => Ok(Indicator::GroupUses),
             "OBJECT BOUNDS" => Ok(Indicator::ObjectBounds),
+            "OBJSENSE" => Ok(Indicator::ObjSense),
+            "OBJNAME" => Ok(Indicator::ObjName),
+            "ELEMENTS" => Ok(Indicator::Elements),
             "ENDATA" => Ok(Indicator::Endata),
             _ => Err(ParseError {
                 message: format!("Unknown indicator: {}", s.trim()),